@@ -348,6 +348,7 @@ pub fn testnet_genesis(
         bridge: Some(BridgeConfig {
             validator_accounts: bridge_validators,
             validators_count: 3u32,
+            emergency_admins: vec![],
             current_limits: vec![
                 100 * 10u128.pow(18),
                 200 * 10u128.pow(18),
@@ -355,9 +356,12 @@ pub fn testnet_genesis(
                 400 * 10u128.pow(18),
                 10 * 10u128.pow(18),
             ],
+            token_limits: vec![],
+            token_eth_addresses: vec![],
         }),
         dao: None,
         token: Some(TokenConfig { tokens }),
+        price_oracle: Some(Default::default()),
     }
 }
 
@@ -0,0 +1,93 @@
+//! Bridge specific RPC methods.
+
+use std::{fmt, sync::Arc};
+
+use akropolisos_runtime::{api::BridgeApi as BridgeRuntimeApi, ProposalId, Status};
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+/// Bridge RPC methods.
+#[rpc]
+pub trait BridgeApi<BlockHash, AccountId, Hash> {
+    /// (proposal id, message id, status, votes, quorum) of every open transfer
+    #[rpc(name = "bridge_pendingTransfers")]
+    fn pending_transfers(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<(ProposalId, Hash, Status, u32, u64)>>;
+
+    /// ids of the open proposals the validator hasn't signed yet
+    #[rpc(name = "bridge_pendingVotesFor")]
+    fn pending_votes_for(
+        &self,
+        validator: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<ProposalId>>;
+}
+
+/// Implements the bridge RPC over the runtime api.
+pub struct Bridge<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Bridge<C, B> {
+    /// Create new `Bridge` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Bridge {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, AccountId, BlockNumber, Balance, Hash, Moment> BridgeApi<<Block as BlockT>::Hash, AccountId, Hash>
+    for Bridge<C, (Block, AccountId, BlockNumber, Balance, Moment)>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static,
+    C: ProvideRuntimeApi<Block>,
+    C: HeaderBackend<Block>,
+    C::Api: BridgeRuntimeApi<Block, AccountId, BlockNumber, Balance, Hash, Moment>,
+    <C::Api as sp_api::ApiErrorExt>::Error: fmt::Debug,
+    AccountId: Codec + Send + Sync + 'static,
+    BlockNumber: Codec + Send + Sync + 'static,
+    Balance: Codec + Send + Sync + 'static,
+    Hash: Codec + Send + Sync + 'static,
+    Moment: Codec + Send + Sync + 'static,
+{
+    fn pending_transfers(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<(ProposalId, Hash, Status, u32, u64)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.pending_transfers(&at).map_err(|e| RpcError {
+            code: ErrorCode::ServerError(1),
+            message: "Unable to query pending transfers.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+
+    fn pending_votes_for(
+        &self,
+        validator: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<ProposalId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.proposals_awaiting(&at, validator)
+            .map(|proposals| proposals.into_iter().map(|(id, _)| id).collect())
+            .map_err(|e| RpcError {
+                code: ErrorCode::ServerError(1),
+                message: "Unable to query pending votes.".into(),
+                data: Some(format!("{:?}", e).into()),
+            })
+    }
+}
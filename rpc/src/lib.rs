@@ -12,9 +12,11 @@
 //! be placed here or imported from corresponding FRAME RPC definitions.
 #![warn(missing_docs)]
 
+pub mod bridge;
+
 use std::{fmt, sync::Arc};
 
-use akropolisos_runtime::{opaque::PrimitiveBlock as Block, BlockNumber, AccountId, Index, Balance};
+use akropolisos_runtime::{opaque::PrimitiveBlock as Block, BlockNumber, AccountId, Index, Balance, Hash, Moment};
 use sc_consensus_babe::{Config, Epoch};
 use sc_consensus_babe_rpc::BabeRPCHandler;
 use sc_consensus_epochs::SharedEpochChanges;
@@ -69,6 +71,7 @@ where
     C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance, sp_runtime::OpaqueExtrinsic>,
     C::Api: BabeApi<Block>,
+    C::Api: akropolisos_runtime::api::BridgeApi<Block, AccountId, BlockNumber, Balance, Hash, Moment>,
     <C::Api as sp_api::ApiErrorExt>::Error: fmt::Debug,
     P: TransactionPool + 'static,
     M: jsonrpc_core::Metadata + Default,
@@ -77,6 +80,7 @@ where
     use pallet_contracts_rpc::{Contracts, ContractsApi};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
     use substrate_frame_rpc_system::{FullSystem, SystemApi};
+    use bridge::{Bridge, BridgeApi};
 
     let mut io = jsonrpc_core::IoHandler::default();
     let FullDeps {
@@ -102,6 +106,9 @@ where
     io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(
         client.clone(),
     )));
+    io.extend_with(BridgeApi::to_delegate(Bridge::<_, (Block, AccountId, BlockNumber, Balance, Moment)>::new(
+        client.clone(),
+    )));
     io.extend_with(sc_consensus_babe_rpc::BabeApi::to_delegate(
         BabeRPCHandler::new(
             client,
@@ -0,0 +1,65 @@
+//! Runtime APIs exposing Akropolis pallets state to the node and its RPC.
+use crate::types::{
+    BridgeConfigSnapshot, BridgeSnapshot, ConfigDiff, Kind, Limits, ProposalId, ProposalMessage,
+    SimulatedOutcome, Status, TokenId,
+};
+use codec::Codec;
+use sp_core::H160;
+use sp_std::prelude::Vec;
+
+sp_api::decl_runtime_apis! {
+    /// Bridge queries for relayers and dashboards.
+    pub trait BridgeApi<AccountId, BlockNumber, Balance, Hash, Moment> where
+        AccountId: Codec,
+        BlockNumber: Codec,
+        Balance: Codec,
+        Hash: Codec,
+        Moment: Codec,
+    {
+        /// (proposal id, kind, votes, quorum) of every open proposal
+        fn open_proposals_progress() -> Vec<(ProposalId, Kind, u64, u64)>;
+        /// (proposal id, message id, status, votes, quorum) of every open transfer
+        fn pending_transfers() -> Vec<(ProposalId, Hash, Status, u32, u64)>;
+        /// confirmed limit changes, newest first
+        fn limit_history() -> Vec<(BlockNumber, Limits<Balance>)>;
+        /// open proposals the validator hasn't voted for yet
+        fn proposals_awaiting(validator: AccountId) -> Vec<(ProposalId, Kind)>;
+        /// config values changed since genesis as (genesis, current) pairs
+        fn config_diff() -> ConfigDiff<AccountId, Balance>;
+        /// dry-run of the daily volume limits for a transfer
+        fn would_exceed_daily(token_id: TokenId, account: AccountId, amount: Balance) -> bool;
+        /// dry-run of every check of set_transfer, naming the one declining the transfer
+        fn simulate_transfer(account: AccountId, to: H160, token_id: TokenId, amount: Balance) -> SimulatedOutcome;
+        /// approved withdraws waiting for ethereum, grouped by (token, destination)
+        fn burn_batch_hints() -> Vec<(TokenId, H160, Vec<Hash>)>;
+        /// distinct tokens the account has bridged
+        fn account_active_tokens(account: AccountId) -> Vec<TokenId>;
+        /// page of open proposals from the cursor id with pending volumes and limits
+        fn full_state_snapshot(cursor: ProposalId, max_items: u32) -> BridgeSnapshot<Hash, Balance>;
+        /// message and kind behind the proposal id
+        fn proposal_message(proposal_id: ProposalId) -> Option<ProposalMessage<AccountId, Hash, Balance>>;
+        /// limits, validators count and quorum in effect at the block
+        fn config_at(block: BlockNumber) -> Option<BridgeConfigSnapshot<Balance>>;
+        /// (inbound, outbound) volume bridged from and to the ethereum address
+        fn eth_address_volume(address: H160) -> (Balance, Balance);
+        /// limits of every registered token
+        fn all_token_limits() -> Vec<(TokenId, Limits<Balance>)>;
+        /// moment the daily volume limits reset
+        fn next_daily_reset() -> Moment;
+    }
+
+    /// Oracle queries for charting front-ends.
+    pub trait OracleApi<Moment, Balance> where
+        Moment: Codec,
+        Balance: Codec,
+    {
+        /// retained (timestamp, price) points of the token, oldest first
+        fn price_points(symbol: Vec<u8>) -> Vec<(Moment, Balance)>;
+        /// (timestamp, price, decimals of the price) of the token's aggregate
+        fn latest_price(symbol: Vec<u8>) -> Option<(Moment, Balance, u8)>;
+        /// time weighted average price of the token over the last `window`
+        fn twap(symbol: Vec<u8>, window: Moment) -> Option<Balance>;
+        /// whether too few prices of the token were recorded to trust its aggregate
+        fn price_warming_up(symbol: Vec<u8>) -> bool;
+    }
+}
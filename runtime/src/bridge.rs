@@ -12,21 +12,44 @@ use crate::token;
 use crate::types::*;
 use codec::Encode;
 use frame_support::{
-    decl_event, decl_module, decl_storage, dispatch::DispatchResult, ensure, fail,
-    weights::SimpleDispatchInfo, StorageMap, StorageValue,
+    debug, decl_event, decl_module, decl_storage, dispatch::DispatchResult, ensure, fail,
+    storage::migration::take_storage_value,
+    traits::{Currency, EnsureOrigin, Get, Imbalance, ReservableCurrency},
+    weights::{DispatchClass, FunctionOf, SimpleDispatchInfo, Weight},
+    StorageMap, StorageValue,
 };
 use num_traits::ops::checked::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub};
 use num_traits::Bounded;
-use sp_core::H160;
-use sp_runtime::traits::Hash;
+use sp_core::{H160, H256};
+use sp_runtime::{
+    traits::{Hash, Saturating, Zero},
+    Perbill,
+};
 use sp_std::prelude::Vec;
-use system::{self, ensure_signed};
+use system::{self, ensure_root, ensure_signed};
 
 type Result<T> = core::result::Result<T, &'static str>;
 
+fn limits_from_config<B: Clone>(limits: &[B]) -> Limits<B> {
+    let mut limits_iter = limits.iter().cloned();
+    Limits {
+        max_tx_value: limits_iter.next().unwrap(),
+        day_max_limit: limits_iter.next().unwrap(),
+        day_max_limit_for_one_address: limits_iter.next().unwrap(),
+        max_pending_tx_limit: limits_iter.next().unwrap(),
+        min_tx_value: limits_iter.next().unwrap(),
+    }
+}
+
 const MAX_VALIDATORS: u32 = 100_000;
 const DAY_IN_BLOCKS: u32 = 14_400;
 const DAY: u32 = 86_400;
+const MAX_LIMIT_HISTORY: usize = 32;
+const MAX_VALIDATOR_SET_HISTORY: usize = 32;
+const DEFAULT_QUORUM: u64 = 2;
+const BASE_WEIGHT: Weight = 10_000;
+const READ_WEIGHT: Weight = 1_000;
+const WRITE_WEIGHT: Weight = 3_000;
 
 decl_event!(
     pub enum Event<T>
@@ -35,54 +58,282 @@ decl_event!(
         Hash = <T as system::Trait>::Hash,
         Balance = <T as balances::Trait>::Balance,
         Moment = <T as timestamp::Trait>::Moment,
+        BlockNumber = <T as system::Trait>::BlockNumber,
     {
-        RelayMessage(Hash),
-        ApprovedRelayMessage(Hash, TokenId, AccountId, H160, Balance),
+        RelayMessage(Hash, ChainId),
+        // (message, chain, token, token contract, from, to, amount)
+        ApprovedRelayMessage(Hash, ChainId, TokenId, H160, AccountId, H160, Balance),
         CancellationConfirmedMessage(Hash, TokenId),
-        MintedMessage(Hash, TokenId),
-        BurnedMessage(Hash, TokenId, AccountId, H160, Balance),
+        MintedMessage(Hash, ChainId, TokenId),
+        BurnedMessage(Hash, ChainId, TokenId, H160, AccountId, H160, Balance),
         AccountPausedMessage(Hash, AccountId, Moment, TokenId),
         AccountResumedMessage(Hash, AccountId, Moment, TokenId),
+        BlockBridgeDigest(BridgeDigest),
+        MintReorgCancelled(Hash, TokenId),
+        EmergencyExitEnabled,
+        EmergencyUnlocked(Hash, TokenId, AccountId, Balance),
+        ContractDestinationTransfer(Hash, H160),
+        ValidatorSetAtMinimum(u32),
+        ValidatorSetBelowSafe(u32, u64),
+        BridgeForcePaused,
+        BridgeForceResumed,
+        PendingCountersReconciled(Balance, Balance, Balance, Balance),
+        BurnReceiptAttached(Hash, H256),
+        TransferOpened(ProposalId, Hash, Kind),
+        PendingMintExpired(Hash, TokenId),
+        ExpiredMessage(Hash, TokenId),
+        // (confirmed, skipped)
+        BatchConfirmProcessed(u32, u32),
+        InvariantViolated(Vec<u8>),
+        VoteRevoked(Hash, AccountId),
+        HoldBypassBondPosted(AccountId, TokenId, Balance),
+        HoldBypassBondReleased(AccountId, TokenId, Balance),
+        HoldBypassBondSlashed(AccountId, TokenId, Balance),
+        ValidatorChangeScheduled(Hash, BlockNumber),
+        ValidatorChangeApplied(Hash),
+        ValidatorChangeCanceled(Hash),
+        // temporary limits in force until the block
+        TemporaryLimitsActive(BlockNumber),
+        TemporaryLimitsExpired,
+        TransferRejected(AccountId, TokenId, RejectionReason),
+        MintBlockedNoPrice(TokenId),
+        TokenAddressUpdated(TokenId, H160),
+        MintScheduled(Hash, BlockNumber),
+        ValidatorSlashed(AccountId, Balance),
+        LimitsChanged(Hash, Limits<Balance>),
+        TransferForceCanceled(Hash, TokenId, AccountId, Balance),
+        // (proposal, validator, votes so far, quorum)
+        ProposalVoted(ProposalId, AccountId, u32, u64),
+        BridgeActionCanceled(Hash),
+        LimitProposalWithdrawn(Hash),
+        ScheduledMintFailed(Hash, TokenId),
     }
 );
 
 pub trait Trait: token::Trait + balances::Trait + system::Trait + timestamp::Trait {
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+
+    /// Blocks a validator counts as active after signing anything.
+    /// Transfers are rejected until quorum validators are active, 0 disables the gate.
+    type ValidatorActivityWindow: Get<Self::BlockNumber>;
+
+    /// Smallest validator set considered safe, reaching it raises an alert.
+    type MinValidators: Get<u32>;
+
+    /// Blocks a newly added validator waits before its votes count.
+    type ValidatorActivationDelay: Get<Self::BlockNumber>;
+
+    /// Blocks a pending mint waits for quorum before it is canceled, 0 disables expiry.
+    type PendingMintTtl: Get<Self::BlockNumber>;
+
+    /// Blocks a withdraw waits for quorum before it is canceled, 0 disables expiry.
+    type TransferTtl: Get<Self::BlockNumber>;
+
+    /// Blocks after genesis during which daily volume limits aren't enforced.
+    type DailyLimitGraceBlocks: Get<Self::BlockNumber>;
+
+    /// Most messages a validator can handle in one batch call.
+    type MaxBatchSize: Get<u32>;
+
+    /// Percent of the balance an account can withdraw on the day of its first mint.
+    type FirstDayWithdrawLimitPercent: Get<u32>;
+
+    /// Native balance an account reserves to skip the first day hold of a token.
+    type HoldBypassBond: Get<Self::Balance>;
+
+    /// Blocks between a confirmed validator set change and its application, 0 applies it at once.
+    type ValidatorChangeDelay: Get<Self::BlockNumber>;
+
+    /// Most deposits a validator can sign in one mint batch.
+    type MaxMintBatch: Get<u32>;
+
+    /// Blocks between a deposit reaching quorum and its mint, 0 mints at once.
+    type MintConfirmationDelay: Get<Self::BlockNumber>;
+
+    /// Part of the free balance a validator loses for approving a withdraw
+    /// that gets canceled, once `SlashingEnabled` is set.
+    type SlashFraction: Get<Perbill>;
+
+    /// External KYC/AML verification of transfers, `()` allows everything.
+    type ComplianceCheck: ComplianceCheck<Self::AccountId, Self::Balance>;
+
+    /// Oracle health of bridged tokens, `()` knows no prices.
+    type TokenPrice: TokenPrice;
+
+    /// Weight of the extrinsics doing more than a single vote.
+    type WeightInfo: WeightInfo;
+}
+
+/// Verifies a substrate account may bridge the amount, `direction` is
+/// `Status::Withdraw` for transfers to ethereum and `Status::Deposit` for mints.
+pub trait ComplianceCheck<AccountId, Balance> {
+    fn verify(account: &AccountId, token_id: TokenId, amount: Balance, direction: Status) -> bool;
+}
+
+impl<AccountId, Balance> ComplianceCheck<AccountId, Balance> for () {
+    fn verify(_: &AccountId, _: TokenId, _: Balance, _: Status) -> bool {
+        true
+    }
+}
+
+/// Tells whether the oracle has a fresh price of the token,
+/// required for mints once `RequireFreshPriceForMint` is set.
+pub trait TokenPrice {
+    fn has_fresh_price(token_id: TokenId) -> bool;
+}
+
+impl TokenPrice for () {
+    fn has_fresh_price(_: TokenId) -> bool {
+        false
+    }
+}
+
+/// Weight of the bridge extrinsics, batches pay for every item.
+pub trait WeightInfo {
+    fn set_transfer() -> Weight;
+    fn multi_signed_mint() -> Weight;
+    fn vote() -> Weight;
+    fn update_validator_list(validators: u32) -> Weight;
+}
+
+/// Storage reads and writes of the calls, executing at quorum included.
+impl WeightInfo for () {
+    fn set_transfer() -> Weight {
+        BASE_WEIGHT + 14 * READ_WEIGHT + 8 * WRITE_WEIGHT
+    }
+    fn multi_signed_mint() -> Weight {
+        BASE_WEIGHT + 12 * READ_WEIGHT + 10 * WRITE_WEIGHT
+    }
+    fn vote() -> Weight {
+        BASE_WEIGHT + 8 * READ_WEIGHT + 6 * WRITE_WEIGHT
+    }
+    fn update_validator_list(validators: u32) -> Weight {
+        Self::vote() + Weight::from(validators) * (2 * READ_WEIGHT + 3 * WRITE_WEIGHT)
+    }
 }
 
 decl_storage! {
     trait Store for Module<T: Trait> as Bridge {
         BridgeIsOperational get(fn bridge_is_operational): bool = true;
+        // paused by an emergency admin, only force_resume brings it back
+        ForcePaused get(fn force_paused): bool;
+        // operators allowed to take emergency actions besides root
+        EmergencyAdmins get(fn emergency_admins) config(): Vec<T::AccountId>;
+        // the bridge is decommissioned, users reclaim funds of unconfirmed withdraws
+        EmergencyExit get(fn emergency_exit): bool;
         BridgeMessages get(fn bridge_messages): map hasher(opaque_blake2_256) T::Hash  => BridgeMessage<T::AccountId, T::Hash>;
 
         // limits change history
         LimitMessages get(fn limit_messages): map hasher(opaque_blake2_256) T::Hash  => LimitMessage<T::Hash, T::Balance>;
+        LimitProposer get(fn limit_proposer): map hasher(opaque_blake2_256) T::Hash => T::AccountId;
+        // (limit proposal, its transfer) each WithdrawLimitProposal bridge message retracts
+        LimitProposalWithdrawals get(fn limit_proposal_withdrawal): map hasher(opaque_blake2_256) T::Hash => Option<(T::Hash, ProposalId)>;
         CurrentLimits get(fn current_limits) build(|config: &GenesisConfig<T>| {
-            let mut limits_iter = config.current_limits.clone().into_iter();
-            Limits {
-                max_tx_value: limits_iter.next().unwrap(),
-                day_max_limit: limits_iter.next().unwrap(),
-                day_max_limit_for_one_address: limits_iter.next().unwrap(),
-                max_pending_tx_limit: limits_iter.next().unwrap(),
-                min_tx_value: limits_iter.next().unwrap(),
-            }
+            limits_from_config(&config.current_limits)
         }): Limits<T::Balance>;
+        // limits of tokens configured apart from the shared CurrentLimits
+        TokenLimits get(fn token_limits) build(|config: &GenesisConfig<T>| {
+            config.token_limits.iter()
+            .map(|(id, limits)| (*id, limits_from_config(limits)))
+            .collect::<Vec<_>>()
+        }): map hasher(opaque_blake2_256) TokenId => Option<Limits<T::Balance>>;
+        // ERC20 contract of each token on ethereum, zero until configured
+        TokenEthAddress get(fn token_eth_address) build(|config: &GenesisConfig<T>| {
+            config.token_eth_addresses.clone()
+        }): map hasher(opaque_blake2_256) TokenId => H160;
+        // (token, contract) proposed by the UpdateTokenAddress bridge message
+        TokenAddressProposals get(fn token_address_proposal): map hasher(opaque_blake2_256) T::Hash => Option<(TokenId, H160)>;
+        // limits replaced by the latest change, restorable by governance
+        PreviousLimits get(fn previous_limits): Option<Limits<T::Balance>>;
+        // confirmed limit changes, oldest first, bounded by MAX_LIMIT_HISTORY
+        LimitHistory get(fn limit_history): Vec<(T::BlockNumber, Limits<T::Balance>)>;
+        // limits in force for blocks [from, until), the replaced ones are kept while active
+        TemporaryLimits get(fn temporary_limits): Option<(Limits<T::Balance>, T::BlockNumber, T::BlockNumber)>;
+        LimitsBeforeTemporary get(fn limits_before_temporary): Option<Limits<T::Balance>>;
 
         // open transactions
         CurrentPendingBurn get(fn pending_burn_count): T::Balance;
         CurrentPendingMint get(fn pending_mint_count): T::Balance;
+        // pending mints to cancel at the block if they still lack quorum
+        PendingMintExpiry get(fn pending_mints_expiring_at): map hasher(opaque_blake2_256) T::BlockNumber => Vec<ProposalId>;
+        // block each approved deposit is minted at, with the index of mints due per block
+        MintExecuteAt get(fn mint_execute_at): map hasher(opaque_blake2_256) ProposalId => Option<T::BlockNumber>;
+        MintsDueAt get(fn mints_due_at): map hasher(opaque_blake2_256) T::BlockNumber => Vec<ProposalId>;
+        // block each withdraw expires at unless it reaches quorum, with the index of withdraws due per block
+        TransferExpiresAt get(fn transfer_expires_at): map hasher(opaque_blake2_256) ProposalId => Option<T::BlockNumber>;
+        TransfersExpiringAt get(fn transfers_expiring_at): map hasher(opaque_blake2_256) T::BlockNumber => Vec<ProposalId>;
 
         BridgeTransfers get(fn transfers): map hasher(opaque_blake2_256) ProposalId => BridgeTransfer<T::Hash>;
         BridgeTransfersCount get(fn bridge_transfers_count): ProposalId;
+        // ids of the transfers which are open for votes
+        OpenProposals get(fn open_proposals): Vec<ProposalId>;
+        // approved withdraws waiting for the ethereum side, their transfers are closed until confirmed
+        ApprovedWithdraws get(fn approved_withdraws): Vec<T::Hash>;
         TransferMessages get(fn messages): map hasher(opaque_blake2_256) T::Hash  => TransferMessage<T::AccountId, T::Hash, T::Balance>;
+        // ethereum messages whose mint is executed, never minted again
+        ProcessedMessages get(fn is_processed_message): map hasher(opaque_blake2_256) T::Hash => bool;
         TransferId get(fn transfer_id_by_hash): map hasher(opaque_blake2_256) T::Hash  => ProposalId;
         MessageId get(fn message_id_by_transfer_id): map hasher(opaque_blake2_256) ProposalId  => T::Hash;
-
-        DailyHolds get(fn daily_holds): map hasher(opaque_blake2_256) T::AccountId  => (T::BlockNumber, T::Hash);
+        // (inbound, outbound) volume bridged from and to each ethereum address
+        EthAddressVolume get(fn eth_address_volume): map hasher(opaque_blake2_256) H160 => (T::Balance, T::Balance);
+        // transfer messages of each substrate account, oldest first
+        AccountTransfers get(fn account_transfers): map hasher(opaque_blake2_256) T::AccountId => Vec<T::Hash>;
+
+        // tokens with the first day withdrawal restriction
+        FirstDayHoldEnabled get(fn first_day_hold_enabled): map hasher(opaque_blake2_256) TokenId => bool = true;
+        DailyHolds get(fn daily_holds): map hasher(opaque_blake2_256) (TokenId, T::AccountId)  => (T::BlockNumber, T::Hash);
+        // holds recorded per account only were moved under every token, new chains start migrated
+        DailyHoldsMigrated get(fn daily_holds_migrated) build(|_| true): bool;
+        // reserved bonds exempting the account from the first day hold of the token
+        HoldBypassBonds get(fn hold_bypass_bond): map hasher(opaque_blake2_256) (TokenId, T::AccountId) => Option<T::Balance>;
         DailyLimits get(fn daily_limits_by_account): map hasher(opaque_blake2_256) (TokenId, T::AccountId)  => T::Balance;
+        // minted to the account per day, capped by day_max_limit_for_one_address like withdraws
+        DailyMints get(fn daily_mints_by_account): map hasher(opaque_blake2_256) (TokenId, T::AccountId, T::Moment)  => T::Balance;
+        // withdraws declined by the daily or pending limits, kept even though the call fails
+        RejectionsByReason get(fn rejections): map hasher(opaque_blake2_256) RejectionReason => u64;
         DailyBlocked get(fn daily_blocked): map hasher(opaque_blake2_256) (TokenId, T::Moment)  => Vec<T::AccountId>;
 
-        Quorum get(fn quorum): u64 = 2;
+        // deprecated tokens can be switched off for good, balances stay as they are
+        TokenTransfersEnabled get(fn token_transfers_enabled): map hasher(opaque_blake2_256) TokenId => bool = true;
+
+        // permissioned tokens can only be minted to allowlisted accounts
+        PermissionedTokens get(fn token_is_permissioned): map hasher(opaque_blake2_256) TokenId => bool;
+        MintRecipientAllowlist get(fn mint_recipient_allowed): map hasher(opaque_blake2_256) (TokenId, T::AccountId) => bool;
+
+        // bridge internal accounts, never a transfer participant
+        ReserveAccount get(fn reserve_account): Option<T::AccountId>;
+        FeeCollector get(fn fee_collector): Option<T::AccountId>;
+
+        // accounts exempt from the pending transactions limit
+        PriorityAccounts get(fn is_priority_account): map hasher(opaque_blake2_256) T::AccountId => bool;
+
+        // ethereum contracts that can't receive a plain token transfer
+        ContractDestinations get(fn is_contract_destination): map hasher(opaque_blake2_256) H160 => bool;
+
+        // ethereum unlock tx fulfilling each confirmed burn
+        BurnReceipts get(fn burn_receipt): map hasher(opaque_blake2_256) T::Hash => Option<H256>;
+        // validators vouching for the receipt, one is enough unless quorum is required
+        ReceiptVotes get(fn receipt_votes): map hasher(opaque_blake2_256) (T::Hash, H256) => Vec<T::AccountId>;
+        ReceiptRequiresQuorum get(fn receipt_requires_quorum): bool;
+
+        // mints of tokens without a fresh oracle price are rejected
+        RequireFreshPriceForMint get(fn require_fresh_price_for_mint): bool;
+
+        // validators confirming a burn, slashed if it is canceled afterwards
+        SlashingEnabled get(fn slashing_enabled): bool;
+        ConfirmVoters get(fn confirm_voters): map hasher(opaque_blake2_256) T::Hash => Vec<T::AccountId>;
+        // status of a withdraw when validators first voted to cancel it
+        CanceledFrom get(fn canceled_from): map hasher(opaque_blake2_256) T::Hash => Option<Status>;
+
+        // validators reporting the ethereum tx of a pending mint as reorged out
+        ReorgVotes get(fn reorg_votes): map hasher(opaque_blake2_256) (T::Hash, T::AccountId) => bool;
+        // reports so far, including validators removed since
+        ReorgVotesCount get(fn reorg_votes_count): map hasher(opaque_blake2_256) T::Hash => MemberId;
+
+        // activity within the current block, emitted and cleared in on_finalize
+        BlockDigest get(fn block_digest): BridgeDigest;
+
+        Quorum get(fn quorum): u64 = DEFAULT_QUORUM;
         ValidatorsCount get(fn validators_count) config(): u32 = 3;
         ValidatorVotes get(fn validator_votes): map hasher(opaque_blake2_256) (ProposalId, T::AccountId) => bool;
         ValidatorHistory get(fn validator_history): map hasher(opaque_blake2_256) T::Hash  => ValidatorMessage<T::AccountId, T::Hash>;
@@ -91,10 +342,30 @@ decl_storage! {
             .map(|acc: T::AccountId| (acc, true)).collect::<Vec<_>>()
         }): map hasher(opaque_blake2_256) T::AccountId  => bool;
         ValidatorAccounts get(fn validator_accounts) config(): Vec<T::AccountId>;
+        // confirmed (validators count, quorum) changes, oldest first, bounded by MAX_VALIDATOR_SET_HISTORY
+        ValidatorSetHistory get(fn validator_set_history): Vec<(T::BlockNumber, (u32, u64))>;
+        // confirmed validator set change waiting for the block it takes effect at
+        PendingValidatorSet get(fn pending_validator_set): Option<(ValidatorMessage<T::AccountId, T::Hash>, T::BlockNumber)>;
+        // limits applied together with the validator set change of the message
+        JointLimits get(fn joint_limits): map hasher(opaque_blake2_256) T::Hash => Option<Limits<T::Balance>>;
+        // (limits, validators, quorum) the chain started with
+        GenesisSnapshot get(fn genesis_snapshot) build(|config: &GenesisConfig<T>| {
+            (
+                limits_from_config(&config.current_limits),
+                config.validator_accounts.clone(),
+                DEFAULT_QUORUM,
+            )
+        }): (Limits<T::Balance>, Vec<T::AccountId>, u64);
+        // block validators added after genesis were registered at
+        ValidatorActiveFrom get(fn validator_active_from): map hasher(opaque_blake2_256) T::AccountId => T::BlockNumber;
+        // block of the latest signature of each validator
+        LastSeen get(fn last_seen): map hasher(opaque_blake2_256) T::AccountId => Option<T::BlockNumber>;
     }
 
     add_extra_genesis{
         config(current_limits): Vec<T::Balance>;
+        config(token_limits): Vec<(TokenId, Vec<T::Balance>)>;
+        config(token_eth_addresses): Vec<(TokenId, H160)>;
     }
 }
 
@@ -104,67 +375,134 @@ decl_module! {
 
         // initiate substrate -> ethereum transfer.
         // create transfer and emit the RelayMessage event
-        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
-        pub fn set_transfer(origin, to: H160, token_id: TokenId, #[compact] amount: T::Balance)-> DispatchResult
+        // transfers to known contracts require allow_contract
+        #[weight = SimpleDispatchInfo::FixedNormal(T::WeightInfo::set_transfer())]
+        pub fn set_transfer(origin, chain_id: ChainId, to: H160, token_id: TokenId, #[compact] amount: T::Balance, allow_contract: bool)-> DispatchResult
         {
             let from = ensure_signed(origin)?;
-            ensure!(Self::bridge_is_operational(), "Bridge is not operational");
-
-            Self::check_amount(amount)?;
-            Self::check_pending_burn(amount)?;
-            Self::check_daily_account_volume(token_id, from.clone(), amount)?;
-
-            let transfer_hash = (&from, &to, amount, <timestamp::Module<T>>::get()).using_encoded(<T as system::Trait>::Hashing::hash);
+            let transfer_hash = (&from, &to, amount, <timestamp::Module<T>>::get(), chain_id).using_encoded(<T as system::Trait>::Hashing::hash);
+            Self::_set_transfer(from, chain_id, to, token_id, amount, allow_contract, transfer_hash, true)?;
+            Ok(())
+        }
 
-            let message = TransferMessage {
-                message_id: transfer_hash,
-                eth_address: to,
-                substrate_address: from.clone(),
-                amount,
-                token: token_id,
-                status: Status::Withdraw,
-                action: Status::Withdraw,
-            };
-            Self::get_transfer_id_checked(transfer_hash, Kind::Transfer)?;
-            Self::deposit_event(RawEvent::RelayMessage(transfer_hash));
+        // several substrate -> ethereum transfers of distinct tokens,
+        // every item is checked before any transfer is created
+        #[weight = FunctionOf(
+            |args: (&ChainId, &H160, &Vec<(TokenId, T::Balance)>)| T::WeightInfo::set_transfer() * args.2.len() as Weight,
+            |_: (&ChainId, &H160, &Vec<(TokenId, T::Balance)>)| DispatchClass::Normal,
+            true
+        )]
+        pub fn set_transfer_multi(origin, chain_id: ChainId, to: H160, items: Vec<(TokenId, T::Balance)>) -> DispatchResult {
+            let from = ensure_signed(origin)?;
+            ensure!(!items.is_empty(), "Batch is empty");
+            ensure!(items.len() as u32 <= T::MaxBatchSize::get(), "Batch is too large");
+            ensure!(!Self::is_contract_destination(to), "Destination is a contract");
+
+            for (i, (token_id, amount)) in items.iter().enumerate() {
+                ensure!(!items[..i].iter().any(|(t, _)| t == token_id), "Duplicate token in batch");
+                Self::count_rejection(&from, to, *token_id, *amount);
+                Self::check_transfer(&from, to, *token_id, *amount)?;
+                ensure!(
+                    !Self::would_exceed_daily(*token_id, &from, *amount),
+                    "Transfer declined, user blocked due to daily volume limit."
+                );
+            }
 
-            <DailyLimits<T>>::mutate((token_id, from), |a| *a += amount);
-            <TransferMessages<T>>::insert(transfer_hash, message);
+            // rejections were counted by the checks above
+            let now = <timestamp::Module<T>>::get();
+            for (token_id, amount) in items {
+                let transfer_hash = (&from, &to, token_id, amount, now, chain_id).using_encoded(<T as system::Trait>::Hashing::hash);
+                Self::_set_transfer(from.clone(), chain_id, to, token_id, amount, false, transfer_hash, false)?;
+            }
             Ok(())
         }
 
         // ethereum-side multi-signed mint operation
-        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
-        pub fn multi_signed_mint(origin, message_id: T::Hash, from: H160, to: T::AccountId, token_id: TokenId, #[compact] amount: T::Balance)-> DispatchResult {
+        #[weight = SimpleDispatchInfo::FixedNormal(T::WeightInfo::multi_signed_mint())]
+        pub fn multi_signed_mint(origin, chain_id: ChainId, message_id: T::Hash, from: H160, to: T::AccountId, token_id: TokenId, #[compact] amount: T::Balance)-> DispatchResult {
             let validator = ensure_signed(origin)?;
             ensure!(Self::bridge_is_operational(), "Bridge is not operational");
 
             Self::check_validator(validator.clone())?;
-            Self::check_pending_mint(amount)?;
-            Self::check_amount(amount)?;
+            ensure!(!from.is_zero(), "Invalid source address");
+            ensure!(!Self::is_processed_message(message_id), "Message already processed");
+            Self::check_message_chain(message_id, chain_id)?;
+            Self::check_pending_mint(&to, token_id, amount)?;
+            Self::check_amount(token_id, amount)?;
 
-            if !<TransferMessages<T>>::contains_key(message_id) {
-                let message = TransferMessage{
-                    message_id,
-                    eth_address: from,
-                    substrate_address: to,
-                    amount,
-                    token: token_id,
-                    status: Status::Deposit,
-                    action: Status::Deposit,
-                };
-                <TransferMessages<T>>::insert(message_id, message);
-                Self::get_transfer_id_checked(message_id, Kind::Transfer)?;
+            Self::_multi_signed_mint(validator, chain_id, message_id, from, to, token_id, amount)
+        }
+
+        // validator signs several deposits at once,
+        // every entry is checked before any of them is signed
+        #[weight = FunctionOf(
+            |args: (&ChainId, &Vec<(T::Hash, H160, T::AccountId, TokenId, T::Balance)>)| {
+                T::WeightInfo::multi_signed_mint() * args.1.len() as Weight
+            },
+            |_: (&ChainId, &Vec<(T::Hash, H160, T::AccountId, TokenId, T::Balance)>)| DispatchClass::Normal,
+            true
+        )]
+        pub fn multi_signed_mint_batch(origin, chain_id: ChainId, mints: Vec<(T::Hash, H160, T::AccountId, TokenId, T::Balance)>) -> DispatchResult {
+            let validator = ensure_signed(origin)?;
+            ensure!(Self::bridge_is_operational(), "Bridge is not operational");
+            Self::check_validator(validator.clone())?;
+            Self::check_validator_active(&validator)?;
+            ensure!(!mints.is_empty(), "Batch is empty");
+            ensure!(mints.len() as u32 <= T::MaxMintBatch::get(), "Batch is too large");
+
+            // pending mint limits apply to the volume of the whole batch
+            let mut batch_volume = T::Balance::zero();
+            // deposits of the batch minted right away, by token and account
+            let mut minted: Vec<(TokenId, T::AccountId, T::Balance)> = Vec::new();
+            let mints_now = T::MintConfirmationDelay::get().is_zero();
+            for (i, (message_id, from, to, token_id, amount)) in mints.iter().enumerate() {
+                ensure!(!mints[..i].iter().any(|m| m.0 == *message_id), "Duplicate message in batch");
+                ensure!(!from.is_zero(), "Invalid source address");
+                ensure!(!Self::is_processed_message(*message_id), "Message already processed");
+                Self::check_message_chain(*message_id, chain_id)?;
+                Self::check_amount(*token_id, *amount)?;
+                if !Self::is_priority_account(to) {
+                    batch_volume = batch_volume.checked_add(amount).ok_or("Overflow adding to new pending mint volume")?;
+                }
+                Self::check_pending_mint(to, *token_id, batch_volume)?;
+                let mut message = (*token_id, to.clone(), *amount);
+                let mut votes = 0;
+                if <TransferId<T>>::contains_key(message_id) {
+                    let transfer_id = <TransferId<T>>::get(message_id);
+                    let transfer = <BridgeTransfers<T>>::get(transfer_id);
+                    ensure!(transfer.open, "This transfer is not open");
+                    ensure!(
+                        !<ValidatorVotes<T>>::get((transfer_id, validator.clone())),
+                        "This validator has already voted."
+                    );
+                    let stored = <TransferMessages<T>>::get(message_id);
+                    message = (stored.token, stored.substrate_address, stored.amount);
+                    votes = transfer.votes;
+                }
+
+                // the deposit runs within this call once the vote reaches quorum
+                if mints_now && Self::votes_are_enough(votes + 1) {
+                    let (token_id, to, amount) = message;
+                    let position = minted.iter().position(|(t, a, _)| *t == token_id && *a == to);
+                    let earlier = position.map(|p| minted[p].2).unwrap_or_else(Zero::zero);
+                    Self::check_deposit(token_id, &to, amount, earlier)?;
+                    let total = earlier.checked_add(&amount).ok_or("Overflow updating daily mint volume")?;
+                    match position {
+                        Some(p) => minted[p].2 = total,
+                        None => minted.push((token_id, to, total)),
+                    }
+                }
             }
 
-            let transfer_id = <TransferId<T>>::get(message_id);
-            Self::_sign(validator, transfer_id)?;
+            for (message_id, from, to, token_id, amount) in mints {
+                Self::_multi_signed_mint(validator.clone(), chain_id, message_id, from, to, token_id, amount)?;
+            }
             Ok(())
         }
 
-        // change maximum tx limit
+        // change maximum tx limit of the token, or the shared limits with no token
         #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
-        pub fn update_limits(origin, max_tx_value: T::Balance, day_max_limit: T::Balance, day_max_limit_for_one_address: T::Balance, max_pending_tx_limit: T::Balance,min_tx_value: T::Balance)-> DispatchResult {
+        pub fn update_limits(origin, token_id: Option<TokenId>, max_tx_value: T::Balance, day_max_limit: T::Balance, day_max_limit_for_one_address: T::Balance, max_pending_tx_limit: T::Balance,min_tx_value: T::Balance)-> DispatchResult {
             let validator = ensure_signed(origin)?;
             Self::check_validator(validator.clone())?;
             let limits = Limits{
@@ -175,15 +513,21 @@ decl_module! {
                 min_tx_value,
             };
             Self::check_limits(&limits)?;
-            let id = (limits.clone(), T::BlockNumber::from(0)).using_encoded(<T as system::Trait>::Hashing::hash);
+            if let Some(token_id) = token_id {
+                ensure!(<token::TokenMap>::contains_key(token_id), "Unknown token");
+            }
+            let id = (limits.clone(), token_id, T::BlockNumber::from(0)).using_encoded(<T as system::Trait>::Hashing::hash);
 
-            if !<LimitMessages<T>>::contains_key(id) {
+            // a withdrawn proposal keeps its message but no longer holds a transfer
+            if !<TransferId<T>>::contains_key(id) {
                 let message = LimitMessage {
                     id,
                     limits,
+                    token: token_id,
                     status: Status::UpdateLimits,
                 };
                 <LimitMessages<T>>::insert(id, message);
+                <LimitProposer<T>>::insert(id, validator.clone());
                 Self::get_transfer_id_checked(id, Kind::Limits)?;
             }
 
@@ -192,8 +536,42 @@ decl_module! {
             Ok(())
         }
 
-        // validator`s response to RelayMessage
+        // proposer retracts limits change that hasn't reached quorum yet,
+        // other validators vote to retract it
         #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn withdraw_limit_proposal(origin, message_id: T::Hash) -> DispatchResult {
+            let validator = ensure_signed(origin)?;
+            Self::check_validator(validator.clone())?;
+            ensure!(<LimitMessages<T>>::contains_key(message_id), "This limit proposal does not exist");
+            ensure!(<TransferId<T>>::contains_key(message_id), "This limit proposal is already withdrawn");
+            let transfer_id = <TransferId<T>>::get(message_id);
+            ensure!(<BridgeTransfers<T>>::get(transfer_id).open, "This transfer is not open");
+
+            if Self::limit_proposer(message_id) == validator {
+                Self::close_limit_proposal(message_id, transfer_id)?;
+                return Ok(());
+            }
+
+            let hash = ("withdraw limit proposal", message_id, transfer_id).using_encoded(<T as system::Trait>::Hashing::hash);
+            if !<BridgeMessages<T>>::contains_key(hash) {
+                let message = BridgeMessage {
+                    message_id: hash,
+                    account: validator.clone(),
+                    action: Status::WithdrawLimitProposal,
+                    status: Status::WithdrawLimitProposal,
+                };
+                <BridgeMessages<T>>::insert(hash, message);
+                <LimitProposalWithdrawals<T>>::insert(hash, (message_id, transfer_id));
+                Self::get_transfer_id_checked(hash, Kind::Bridge)?;
+            }
+
+            let id = <TransferId<T>>::get(hash);
+            Self::_sign(validator, id)?;
+            Ok(())
+        }
+
+        // validator`s response to RelayMessage
+        #[weight = SimpleDispatchInfo::FixedNormal(T::WeightInfo::vote())]
         pub fn approve_transfer(origin, message_id: T::Hash) -> DispatchResult {
             let validator = ensure_signed(origin)?;
             ensure!(Self::bridge_is_operational(), "Bridge is not operational");
@@ -205,18 +583,32 @@ decl_module! {
         }
 
         // each validator calls it to update whole set of validators
-        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        #[weight = FunctionOf(
+            |args: (&T::Hash, &u64, &Vec<T::AccountId>)| T::WeightInfo::update_validator_list(args.2.len() as u32),
+            |_: (&T::Hash, &u64, &Vec<T::AccountId>)| DispatchClass::Normal,
+            true
+        )]
         pub fn update_validator_list(origin, message_id: T::Hash, quorum: u64, new_validator_list: Vec<T::AccountId>) -> DispatchResult {
+            let validator = ensure_signed(origin)?;
+            Self::propose_validator_set(validator, message_id, quorum, new_validator_list, None)?;
+            Ok(())
+        }
+
+        // quorum change keeping the current validator set
+        #[weight = SimpleDispatchInfo::FixedNormal(T::WeightInfo::vote())]
+        pub fn update_quorum(origin, message_id: T::Hash, new_quorum: u64) -> DispatchResult {
             let validator = ensure_signed(origin)?;
             Self::check_validator(validator.clone())?;
+            Self::check_quorum_fits(new_quorum)?;
+            ensure!(Self::pending_validator_set().is_none(), "Validator set change already scheduled");
 
             if !<ValidatorHistory<T>>::contains_key(message_id) {
                 let message = ValidatorMessage {
                     message_id,
-                    quorum,
-                    accounts: new_validator_list,
-                    action: Status::UpdateValidatorSet,
-                    status: Status::UpdateValidatorSet,
+                    quorum: new_quorum,
+                    accounts: Vec::new(),
+                    action: Status::UpdateQuorum,
+                    status: Status::UpdateQuorum,
                 };
                 <ValidatorHistory<T>>::insert(message_id, message);
                 Self::get_transfer_id_checked(message_id, Kind::Validator)?;
@@ -227,6 +619,72 @@ decl_module! {
             Ok(())
         }
 
+        // validator set and limits change applied together at quorum
+        #[weight = FunctionOf(
+            |args: (&T::Hash, &Vec<T::AccountId>, &u64, &Limits<T::Balance>)| {
+                T::WeightInfo::update_validator_list(args.1.len() as u32) + WRITE_WEIGHT
+            },
+            |_: (&T::Hash, &Vec<T::AccountId>, &u64, &Limits<T::Balance>)| DispatchClass::Normal,
+            true
+        )]
+        pub fn joint_governance_update(origin, message_id: T::Hash, validators: Vec<T::AccountId>, quorum: u64, limits: Limits<T::Balance>) -> DispatchResult {
+            let validator = ensure_signed(origin)?;
+            Self::check_limits(&limits)?;
+            Self::propose_validator_set(validator, message_id, quorum, validators, Some(limits))?;
+            Ok(())
+        }
+
+        // each validator calls it to set the ERC20 contract of the token
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn update_token_eth_address(origin, token_id: TokenId, address: H160) -> DispatchResult {
+            let validator = ensure_signed(origin)?;
+            Self::check_validator(validator.clone())?;
+            ensure!(<token::TokenMap>::contains_key(token_id), "Unknown token");
+            ensure!(!address.is_zero(), "Invalid token address");
+            let hash = ("update token address", token_id, address).using_encoded(<T as system::Trait>::Hashing::hash);
+
+            if !<BridgeMessages<T>>::contains_key(hash) {
+                let message = BridgeMessage {
+                    message_id: hash,
+                    account: validator.clone(),
+                    action: Status::UpdateTokenAddress,
+                    status: Status::UpdateTokenAddress,
+                };
+                <BridgeMessages<T>>::insert(hash, message);
+                <TokenAddressProposals<T>>::insert(hash, (token_id, address));
+                Self::get_transfer_id_checked(hash, Kind::Bridge)?;
+            }
+
+            let id = <TransferId<T>>::get(hash);
+            Self::_sign(validator, id)?;
+            Ok(())
+        }
+
+        // each validator calls it to cancel the scheduled validator set change
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn cancel_validator_change(origin) -> DispatchResult {
+            let validator = ensure_signed(origin)?;
+            Self::check_validator(validator.clone())?;
+
+            let (pending, _) = Self::pending_validator_set().ok_or("No validator set change scheduled")?;
+            let hash = ("cancel validator change", pending.message_id).using_encoded(<T as system::Trait>::Hashing::hash);
+
+            if !<BridgeMessages<T>>::contains_key(hash) {
+                let message = BridgeMessage {
+                    message_id: hash,
+                    account: validator.clone(),
+                    action: Status::CancelValidatorChange,
+                    status: Status::CancelValidatorChange,
+                };
+                <BridgeMessages<T>>::insert(hash, message);
+                Self::get_transfer_id_checked(hash, Kind::Bridge)?;
+            }
+
+            let id = <TransferId<T>>::get(hash);
+            Self::_sign(validator, id)?;
+            Ok(())
+        }
+
         // each validator calls it to pause the bridge
         #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
         pub fn pause_bridge(origin) -> DispatchResult {
@@ -257,6 +715,8 @@ decl_module! {
         pub fn resume_bridge(origin) -> DispatchResult {
             let validator = ensure_signed(origin)?;
             Self::check_validator(validator.clone())?;
+            ensure!(!Self::emergency_exit(), "Bridge is decommissioned");
+            ensure!(!Self::force_paused(), "Bridge is force-paused; requires force_resume.");
 
             let hash = ("resume", T::BlockNumber::from(0)).using_encoded(<T as system::Trait>::Hashing::hash);
 
@@ -276,22 +736,69 @@ decl_module! {
             Ok(())
         }
 
-        //confirm burn from validator
+        // validator abandons the open pause or resume proposal
         #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn cancel_bridge_action(origin) -> DispatchResult {
+            let validator = ensure_signed(origin)?;
+            Self::check_validator(validator)?;
+
+            let action = if Self::bridge_is_operational() { "pause" } else { "resume" };
+            let hash = (action, T::BlockNumber::from(0)).using_encoded(<T as system::Trait>::Hashing::hash);
+            ensure!(<BridgeMessages<T>>::contains_key(hash), "No bridge action to cancel");
+            ensure!(
+                <BridgeMessages<T>>::get(hash).status != Status::Confirmed,
+                "Bridge action is already confirmed"
+            );
+
+            let id = <TransferId<T>>::get(hash);
+            let mut transfer = <BridgeTransfers<T>>::get(id);
+            transfer.open = false;
+            transfer.votes = 0;
+            Self::index_open_proposal(id, false);
+            for account in Self::validator_accounts() {
+                <ValidatorVotes<T>>::remove((id, account));
+            }
+            <BridgeTransfers<T>>::insert(id, transfer);
+            <BridgeMessages<T>>::remove(hash);
+            <TransferId<T>>::remove(hash);
+            Self::deposit_event(RawEvent::BridgeActionCanceled(hash));
+            Ok(())
+        }
+
+        //confirm burn from validator
+        #[weight = SimpleDispatchInfo::FixedNormal(T::WeightInfo::vote())]
         pub fn confirm_transfer(origin, message_id: T::Hash) -> DispatchResult {
             let validator = ensure_signed(origin)?;
             ensure!(Self::bridge_is_operational(), "Bridge is not operational");
             Self::check_validator(validator.clone())?;
 
-            let id = <TransferId<T>>::get(message_id);
-
-            let is_approved = <TransferMessages<T>>::get(message_id).status == Status::Approved ||
-            <TransferMessages<T>>::get(message_id).status == Status::Confirmed;
-            ensure!(is_approved, "This transfer must be approved first.");
+            Self::_confirm_transfer(validator, message_id)?;
+            Ok(())
+        }
 
-            Self::update_status(message_id, Status::Confirmed, Kind::Transfer)?;
-            Self::reopen_for_burn_confirmation(message_id)?;
-            Self::_sign(validator, id)?;
+        //confirm several burns at once, invalid ones are skipped
+        #[weight = FunctionOf(
+            |args: (&Vec<T::Hash>,)| T::WeightInfo::vote() * args.0.len() as Weight,
+            |_: (&Vec<T::Hash>,)| DispatchClass::Normal,
+            true
+        )]
+        pub fn confirm_transfer_batch(origin, message_ids: Vec<T::Hash>) -> DispatchResult {
+            let validator = ensure_signed(origin)?;
+            ensure!(Self::bridge_is_operational(), "Bridge is not operational");
+            Self::check_validator(validator.clone())?;
+            ensure!(message_ids.len() as u32 <= T::MaxBatchSize::get(), "Batch is too large");
+
+            let mut confirmed = 0u32;
+            let mut skipped = 0u32;
+            for message_id in message_ids {
+                let confirmable = Self::can_confirm(&validator, message_id);
+                if confirmable && Self::_confirm_transfer(validator.clone(), message_id).is_ok() {
+                    confirmed += 1;
+                } else {
+                    skipped += 1;
+                }
+            }
+            Self::deposit_event(RawEvent::BatchConfirmProcessed(confirmed, skipped));
             Ok(())
         }
 
@@ -301,81 +808,776 @@ decl_module! {
             let validator = ensure_signed(origin)?;
             Self::check_validator(validator.clone())?;
 
-            let has_burned = <TransferMessages<T>>::contains_key(message_id) && <TransferMessages<T>>::get(message_id).status == Status::Confirmed;
+            let id = <TransferId<T>>::get(message_id);
+            let is_confirmed = <TransferMessages<T>>::contains_key(message_id) && <TransferMessages<T>>::get(message_id).status == Status::Confirmed;
+            let has_burned = is_confirmed && !<BridgeTransfers<T>>::get(id).open;
             ensure!(!has_burned, "Failed to cancel. This transfer is already executed.");
 
-            let id = <TransferId<T>>::get(message_id);
+            // votes to confirm the burn don't count towards its cancellation
+            if is_confirmed {
+                <BridgeTransfers<T>>::mutate(id, |t| t.open = false);
+            }
+            let status = <TransferMessages<T>>::get(message_id).status;
+            if status != Status::Canceled {
+                <CanceledFrom<T>>::insert(message_id, status);
+            }
             Self::update_status(message_id, Status::Canceled, Kind::Transfer)?;
             Self::reopen_for_burn_confirmation(message_id)?;
             Self::_sign(validator, id)?;
             Ok(())
         }
 
-        //close enough to clear it exactly at UTC 00:00 instead of BlockNumber
-        fn on_finalize() {
-            // clear accounts blocked day earlier (e.g. 18759 - 1)
-            let yesterday = Self::get_day_pair().0;
-            let is_first_day = Self::get_day_pair().1 == yesterday;
-            let tokens = <token::Module<T>>::tokens();
-            for t in tokens {
-                if <DailyBlocked<T>>::contains_key((t.id, yesterday)) && !is_first_day {
-                    let blocked_yesterday = <DailyBlocked<T>>::get((t.id, yesterday));
-                blocked_yesterday.iter().for_each(|a| <DailyLimits<T>>::remove((t.id, a)));
-                blocked_yesterday.iter().for_each(|a|{
-                    let now = <timestamp::Module<T>>::get();
-                    let hash = (now.clone(), a.clone()).using_encoded(<T as system::Trait>::Hashing::hash);
-                    Self::deposit_event(RawEvent::AccountResumedMessage(hash, a.clone(), now, t.id));
-                }
-                );
-                    <DailyBlocked<T>>::remove((t.id, yesterday));
+        // validator takes back its vote on a proposal that hasn't reached quorum
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn revoke_vote(origin, message_id: T::Hash) -> DispatchResult {
+            let validator = ensure_signed(origin)?;
+            Self::check_validator(validator.clone())?;
+            ensure!(<TransferId<T>>::contains_key(message_id), "Unknown message");
+
+            let id = <TransferId<T>>::get(message_id);
+            let mut transfer = <BridgeTransfers<T>>::get(id);
+            ensure!(transfer.open, "This transfer is not open");
+            ensure!(<ValidatorVotes<T>>::get((id, validator.clone())), "This validator has not voted.");
+
+            transfer.votes = transfer.votes.checked_sub(1).ok_or("Overflow revoking the vote")?;
+            if transfer.votes == 0 && transfer.kind == Kind::Transfer {
+                Self::unset_pending(message_id)?;
             }
+            <ValidatorVotes<T>>::remove((id, validator.clone()));
+            <BridgeTransfers<T>>::insert(id, transfer);
+            Self::deposit_event(RawEvent::VoteRevoked(message_id, validator));
+            Ok(())
         }
-    }
-}
-}
 
-impl<T: Trait> Module<T> {
-    fn _sign(validator: T::AccountId, transfer_id: ProposalId) -> Result<()> {
-        let mut transfer = <BridgeTransfers<T>>::get(transfer_id);
+        // validator reports that the ethereum tx behind a mint was reorged out
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn report_eth_reorg(origin, message_id: T::Hash) -> DispatchResult {
+            let validator = ensure_signed(origin)?;
+            Self::check_validator(validator.clone())?;
+            Self::check_validator_active(&validator)?;
+
+            ensure!(<TransferMessages<T>>::contains_key(message_id), "This transfer does not exist");
+            let message = <TransferMessages<T>>::get(message_id);
+            ensure!(message.action == Status::Deposit, "Only mints can be reorged");
+            ensure!(message.status != Status::Confirmed, "Mint is already executed, reorg can't be undone");
+            ensure!(message.status != Status::Canceled, "Mint is already canceled");
+
+            let voted = <ReorgVotes<T>>::get((message_id, validator.clone()));
+            ensure!(!voted, "This validator has already reported the reorg.");
+            <ReorgVotes<T>>::insert((message_id, validator), true);
+
+            let reported = <ReorgVotesCount<T>>::get(message_id)
+                .checked_add(1)
+                .ok_or("Overflow adding reorg report")?;
+            <ReorgVotesCount<T>>::insert(message_id, reported);
+            // reports of validators removed since don't count towards the quorum
+            let votes = Self::validator_accounts()
+                .into_iter()
+                .filter(|v| <ReorgVotes<T>>::get((message_id, v.clone())))
+                .count() as MemberId;
+            if Self::votes_are_enough(votes) {
+                Self::cancel_reorged_mint(message)?;
+            }
+            Ok(())
+        }
 
-        let mut message = <TransferMessages<T>>::get(transfer.message_id);
-        let mut limit_message = <LimitMessages<T>>::get(transfer.message_id);
-        let mut validator_message = <ValidatorHistory<T>>::get(transfer.message_id);
-        let mut bridge_message = <BridgeMessages<T>>::get(transfer.message_id);
-        let voted = <ValidatorVotes<T>>::get((transfer_id, validator.clone()));
-        ensure!(!voted, "This validator has already voted.");
-        ensure!(transfer.open, "This transfer is not open");
-        transfer.votes += 1;
+        // validator records the ethereum tx which unlocked a confirmed burn
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn attach_eth_receipt(origin, message_id: T::Hash, eth_tx: H256) -> DispatchResult {
+            let validator = ensure_signed(origin)?;
+            Self::check_validator(validator.clone())?;
 
-        if Self::votes_are_enough(transfer.votes) {
-            match message.status {
-                Status::Confirmed | Status::Canceled => (), // if burn is confirmed or canceled
-                _ => match transfer.kind {
-                    Kind::Transfer => message.status = Status::Approved,
-                    Kind::Limits => limit_message.status = Status::Approved,
-                    Kind::Validator => validator_message.status = Status::Approved,
-                    Kind::Bridge => bridge_message.status = Status::Approved,
-                },
-            }
-            match transfer.kind {
-                Kind::Transfer => Self::execute_transfer(message)?,
-                Kind::Limits => Self::_update_limits(limit_message)?,
-                Kind::Validator => Self::manage_validator_list(validator_message)?,
-                Kind::Bridge => Self::manage_bridge(bridge_message)?,
+            ensure!(<TransferMessages<T>>::contains_key(message_id), "This transfer does not exist");
+            let message = <TransferMessages<T>>::get(message_id);
+            ensure!(message.action == Status::Withdraw, "Only burns have ethereum receipts");
+            ensure!(message.status == Status::Confirmed, "Burn is not confirmed yet");
+            ensure!(Self::burn_receipt(message_id).is_none(), "Receipt is already attached");
+
+            let mut votes = Self::receipt_votes((message_id, eth_tx));
+            ensure!(!votes.contains(&validator), "This validator has already attached the receipt.");
+            votes.push(validator);
+
+            let required = if Self::receipt_requires_quorum() { Self::quorum() } else { 1 };
+            if votes.len() as u64 >= required {
+                <ReceiptVotes<T>>::remove((message_id, eth_tx));
+                <BurnReceipts<T>>::insert(message_id, eth_tx);
+                Self::deposit_event(RawEvent::BurnReceiptAttached(message_id, eth_tx));
+            } else {
+                <ReceiptVotes<T>>::insert((message_id, eth_tx), votes);
             }
-            transfer.open = false;
-        } else {
-            match message.status {
-                Status::Confirmed | Status::Canceled => (),
-                _ => Self::set_pending(transfer_id, transfer.kind.clone())?,
-            };
+            Ok(())
         }
 
-        <ValidatorVotes<T>>::mutate((transfer_id, validator), |a| *a = true);
-        <BridgeTransfers<T>>::insert(transfer_id, transfer);
+        // governance chooses whether receipts need validators quorum
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn set_receipt_requires_quorum(origin, required: bool) -> DispatchResult {
+            ensure_root(origin)?;
+            <ReceiptRequiresQuorum>::put(required);
+            Ok(())
+        }
 
-        Ok(())
-    }
+        // governance ties mints to the oracle having a fresh price of the token
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn set_require_fresh_price_for_mint(origin, required: bool) -> DispatchResult {
+            ensure_root(origin)?;
+            <RequireFreshPriceForMint>::put(required);
+            Ok(())
+        }
+
+        // governance turns slashing of validators approving canceled withdraws on or off
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn set_slashing_enabled(origin, enabled: bool) -> DispatchResult {
+            ensure_root(origin)?;
+            <SlashingEnabled>::put(enabled);
+            Ok(())
+        }
+
+        // governance rolls back the latest limits change
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn restore_previous_limits(origin) -> DispatchResult {
+            ensure_root(origin)?;
+            let previous = Self::previous_limits().ok_or("No previous limits to restore")?;
+            Self::set_current_limits(previous);
+            Ok(())
+        }
+
+        // governance raises limits for a window, reverted automatically after it
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn schedule_temporary_limits(origin, limits: Limits<T::Balance>, from_block: T::BlockNumber, until_block: T::BlockNumber) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::check_limits(&limits)?;
+            ensure!(Self::temporary_limits().is_none(), "Temporary limits already scheduled");
+            ensure!(from_block > <system::Module<T>>::block_number(), "Temporary limits must start in a future block");
+            ensure!(from_block < until_block, "Temporary limits window is empty");
+            <TemporaryLimits<T>>::put((limits, from_block, until_block));
+            Ok(())
+        }
+
+        // governance fixes drift of the pending counters from the open transfers
+        #[weight = SimpleDispatchInfo::FixedNormal(100_000)]
+        pub fn recompute_pending_counters(origin) -> DispatchResult {
+            ensure_root(origin)?;
+            let (new_burn, new_mint) = Self::open_pending_volumes()?;
+            let old_burn = <CurrentPendingBurn<T>>::get();
+            let old_mint = <CurrentPendingMint<T>>::get();
+            <CurrentPendingBurn<T>>::put(new_burn);
+            <CurrentPendingMint<T>>::put(new_mint);
+            Self::deposit_event(RawEvent::PendingCountersReconciled(old_burn, new_burn, old_mint, new_mint));
+            Ok(())
+        }
+
+        // testnet consistency check, emits an event per broken invariant
+        #[weight = SimpleDispatchInfo::FixedNormal(100_000)]
+        pub fn check_invariants(origin) -> DispatchResult {
+            ensure_root(origin)?;
+            let violations = Self::invariant_violations()?;
+            if violations.is_empty() {
+                return Ok(());
+            }
+            for violation in violations {
+                Self::deposit_event(RawEvent::InvariantViolated(violation.as_bytes().to_vec()));
+            }
+            fail!("Bridge invariants violated")
+        }
+
+        // emergency stop of the bridge without waiting for validators quorum
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn force_pause(origin) -> DispatchResult {
+            EnsureEmergencyAdmin::<T>::ensure_origin(origin)?;
+            <BridgeIsOperational>::put(false);
+            <ForcePaused>::put(true);
+            Self::deposit_event(RawEvent::BridgeForcePaused);
+            Ok(())
+        }
+
+        // lifts the emergency stop, quorum resume_bridge can't override it
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn force_resume(origin) -> DispatchResult {
+            EnsureEmergencyAdmin::<T>::ensure_origin(origin)?;
+            ensure!(Self::force_paused(), "Bridge is not force-paused");
+            ensure!(!Self::emergency_exit(), "Bridge is decommissioned");
+            <ForcePaused>::put(false);
+            <BridgeIsOperational>::put(true);
+            Self::deposit_event(RawEvent::BridgeForceResumed);
+            Ok(())
+        }
+
+        // governance cancels a stuck withdraw without validators quorum, releasing its funds
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn force_cancel_transfer(origin, message_id: T::Hash) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(<TransferMessages<T>>::contains_key(message_id), "This transfer does not exist");
+            let message = <TransferMessages<T>>::get(message_id);
+            ensure!(message.action == Status::Withdraw, "Only withdraws can be force-canceled");
+            if !Self::withdraw_in_flight(&message) {
+                match message.status {
+                    Status::Confirmed => fail!("Failed to cancel. This transfer is already executed."),
+                    _ => fail!("This transfer is already canceled"),
+                }
+            }
+
+            Self::release_withdraw(&message)?;
+            <ConfirmVoters<T>>::remove(message_id);
+            <BlockDigest>::mutate(|d| d.cancellations += 1);
+            Self::deposit_event(RawEvent::TransferForceCanceled(
+                message_id,
+                message.token,
+                message.substrate_address,
+                message.amount,
+            ));
+            Ok(())
+        }
+
+        // governance manages emergency administrators
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn add_emergency_admin(origin, account: T::AccountId) -> DispatchResult {
+            ensure_root(origin)?;
+            let mut admins = Self::emergency_admins();
+            ensure!(!admins.contains(&account), "Account is already an emergency admin");
+            admins.push(account);
+            <EmergencyAdmins<T>>::put(admins);
+            Ok(())
+        }
+
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn remove_emergency_admin(origin, account: T::AccountId) -> DispatchResult {
+            ensure_root(origin)?;
+            <EmergencyAdmins<T>>::mutate(|admins| admins.retain(|a| *a != account));
+            Ok(())
+        }
+
+        // one-way switch to decommission the bridge
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn enable_emergency_exit(origin) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(!Self::emergency_exit(), "Emergency exit is already enabled");
+            <EmergencyExit>::put(true);
+            <BridgeIsOperational>::put(false);
+            Self::deposit_event(RawEvent::EmergencyExitEnabled);
+            Ok(())
+        }
+
+        // user reclaims funds of own withdraw that never got confirmed
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn emergency_unlock(origin, message_id: T::Hash) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::emergency_exit(), "Emergency exit is not enabled");
+            ensure!(<TransferMessages<T>>::contains_key(message_id), "This transfer does not exist");
+
+            let message = <TransferMessages<T>>::get(message_id);
+            ensure!(message.action == Status::Withdraw, "Only withdraws can be unlocked");
+            ensure!(message.substrate_address == who, "Only the owner can unlock the transfer");
+            ensure!(Self::withdraw_in_flight(&message), "This transfer has nothing to unlock");
+
+            Self::release_withdraw(&message)?;
+            Self::deposit_event(RawEvent::EmergencyUnlocked(message_id, message.token, who, message.amount));
+            Ok(())
+        }
+
+        // governance sets the bridge reserve account
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn set_reserve_account(origin, account: Option<T::AccountId>) -> DispatchResult {
+            ensure_root(origin)?;
+            <ReserveAccount<T>>::set(account);
+            Ok(())
+        }
+
+        // governance sets the bridge fee collector account
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn set_fee_collector(origin, account: Option<T::AccountId>) -> DispatchResult {
+            ensure_root(origin)?;
+            <FeeCollector<T>>::set(account);
+            Ok(())
+        }
+
+        // governance manages accounts exempt from the pending transactions limit
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn set_priority_account(origin, account: T::AccountId, priority: bool) -> DispatchResult {
+            ensure_root(origin)?;
+            if priority {
+                <PriorityAccounts<T>>::insert(account, true);
+            } else {
+                <PriorityAccounts<T>>::remove(account);
+            }
+            Ok(())
+        }
+
+        // governance flags ethereum contracts as transfer destinations
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn set_contract_destination(origin, address: H160, flagged: bool) -> DispatchResult {
+            ensure_root(origin)?;
+            if flagged {
+                <ContractDestinations>::insert(address, true);
+            } else {
+                <ContractDestinations>::remove(address);
+            }
+            Ok(())
+        }
+
+        // governance toggles the first day withdrawal hold of a token
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn set_first_day_hold(origin, token_id: TokenId, enabled: bool) -> DispatchResult {
+            ensure_root(origin)?;
+            <FirstDayHoldEnabled>::insert(token_id, enabled);
+            Ok(())
+        }
+
+        // account reserves a bond to skip the first day hold of the token
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn post_hold_bypass_bond(origin, token_id: TokenId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(<token::TokenMap>::contains_key(token_id), "Unknown token");
+            ensure!(!<HoldBypassBonds<T>>::contains_key((token_id, who.clone())), "Hold bypass bond already posted");
+
+            let bond = T::HoldBypassBond::get();
+            <balances::Module<T> as ReservableCurrency<T::AccountId>>::reserve(&who, bond)?;
+            <HoldBypassBonds<T>>::insert((token_id, who.clone()), bond);
+            Self::deposit_event(RawEvent::HoldBypassBondPosted(who, token_id, bond));
+            Ok(())
+        }
+
+        // account takes its bond back, its withdraws are on hold again
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn release_hold_bypass_bond(origin, token_id: TokenId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let bond = <HoldBypassBonds<T>>::take((token_id, who.clone())).ok_or("No hold bypass bond posted")?;
+            <balances::Module<T> as ReservableCurrency<T::AccountId>>::unreserve(&who, bond);
+            Self::deposit_event(RawEvent::HoldBypassBondReleased(who, token_id, bond));
+            Ok(())
+        }
+
+        // governance slashes the bond of an account confirmed to have committed fraud
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn slash_hold_bypass_bond(origin, token_id: TokenId, account: T::AccountId) -> DispatchResult {
+            ensure_root(origin)?;
+            let bond = <HoldBypassBonds<T>>::take((token_id, account.clone())).ok_or("No hold bypass bond posted")?;
+            let _ = <balances::Module<T> as ReservableCurrency<T::AccountId>>::slash_reserved(&account, bond);
+            Self::deposit_event(RawEvent::HoldBypassBondSlashed(account, token_id, bond));
+            Ok(())
+        }
+
+        // governance enables or disables bridging of a token
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn set_token_transfers_enabled(origin, token_id: TokenId, enabled: bool) -> DispatchResult {
+            ensure_root(origin)?;
+            <TokenTransfersEnabled>::insert(token_id, enabled);
+            Ok(())
+        }
+
+        // governance marks a token as permissioned
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn set_token_permissioned(origin, token_id: TokenId, permissioned: bool) -> DispatchResult {
+            ensure_root(origin)?;
+            <PermissionedTokens>::insert(token_id, permissioned);
+            Ok(())
+        }
+
+        // governance manages recipients allowed to receive mints of a permissioned token
+        #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+        pub fn set_mint_recipient(origin, token_id: TokenId, account: T::AccountId, allowed: bool) -> DispatchResult {
+            ensure_root(origin)?;
+            if allowed {
+                <MintRecipientAllowlist<T>>::insert((token_id, account), true);
+            } else {
+                <MintRecipientAllowlist<T>>::remove((token_id, account));
+            }
+            Ok(())
+        }
+
+        fn on_runtime_upgrade() {
+            if !Self::daily_holds_migrated() {
+                Self::migrate_daily_holds();
+                <DailyHoldsMigrated>::put(true);
+            }
+        }
+
+        //close enough to clear it exactly at UTC 00:00 instead of BlockNumber
+        fn on_initialize(n: T::BlockNumber) {
+            Self::apply_pending_validator_set(n);
+            Self::apply_temporary_limits(n);
+        }
+
+        fn on_finalize() {
+            Self::execute_due_mints(<system::Module<T>>::block_number());
+            Self::expire_pending_mints(<system::Module<T>>::block_number());
+            Self::expire_transfers(<system::Module<T>>::block_number());
+
+            // clear accounts blocked day earlier (e.g. 18759 - 1)
+            let yesterday = Self::get_day_pair().0;
+            let is_first_day = Self::get_day_pair().1 == yesterday;
+            let tokens = <token::Module<T>>::tokens();
+            for t in tokens {
+                if <DailyBlocked<T>>::contains_key((t.id, yesterday)) && !is_first_day {
+                    let blocked_yesterday = <DailyBlocked<T>>::get((t.id, yesterday));
+                    blocked_yesterday.iter().for_each(|a| <DailyLimits<T>>::remove((t.id, a)));
+                    blocked_yesterday.iter().for_each(|a| {
+                        let now = <timestamp::Module<T>>::get();
+                        let hash = (now.clone(), a.clone()).using_encoded(<T as system::Trait>::Hashing::hash);
+                        Self::deposit_event(RawEvent::AccountResumedMessage(hash, a.clone(), now, t.id));
+                    });
+                    <DailyBlocked<T>>::remove((t.id, yesterday));
+                }
+            }
+
+            let digest = <BlockDigest>::take();
+            if digest != BridgeDigest::default() {
+                Self::deposit_event(RawEvent::BlockBridgeDigest(digest));
+            }
+        }
+    }
+}
+
+/// Root or one of the bridge emergency administrators.
+/// Succeeds with the admin account, `None` for root.
+pub struct EnsureEmergencyAdmin<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Trait> EnsureOrigin<T::Origin> for EnsureEmergencyAdmin<T> {
+    type Success = Option<T::AccountId>;
+
+    fn try_origin(o: T::Origin) -> core::result::Result<Self::Success, T::Origin> {
+        o.into().and_then(|o| match o {
+            system::RawOrigin::Root => Ok(None),
+            system::RawOrigin::Signed(who) if <Module<T>>::emergency_admins().contains(&who) => {
+                Ok(Some(who))
+            }
+            r => Err(T::Origin::from(r)),
+        })
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// open proposals in id order, read from the index rather than every transfer
+    fn open_transfers() -> Vec<BridgeTransfer<T::Hash>> {
+        let mut ids = Self::open_proposals();
+        ids.sort();
+        ids.into_iter().map(|id| <BridgeTransfers<T>>::get(id)).collect()
+    }
+
+    /// (proposal id, kind, votes, quorum) of every open proposal
+    pub fn open_proposals_progress() -> Vec<(ProposalId, Kind, u64, u64)> {
+        let quorum = Self::quorum();
+        Self::open_transfers()
+            .into_iter()
+            .map(|t| (t.transfer_id, t.kind, t.votes, quorum))
+            .collect()
+    }
+
+    /// (proposal id, message id, status, votes, quorum) of every open transfer
+    pub fn pending_transfers() -> Vec<(ProposalId, T::Hash, Status, u32, u64)> {
+        let quorum = Self::quorum();
+        Self::open_transfers()
+            .into_iter()
+            .map(|t| {
+                let status = Self::proposal_status(&t);
+                (t.transfer_id, t.message_id, status, t.votes as u32, quorum)
+            })
+            .collect()
+    }
+
+    /// status of the message behind the proposal, looked up by its kind
+    fn proposal_status(transfer: &BridgeTransfer<T::Hash>) -> Status {
+        match transfer.kind {
+            Kind::Transfer => <TransferMessages<T>>::get(transfer.message_id).status,
+            Kind::Limits => <LimitMessages<T>>::get(transfer.message_id).status,
+            Kind::Validator => <ValidatorHistory<T>>::get(transfer.message_id).status,
+            Kind::Bridge => <BridgeMessages<T>>::get(transfer.message_id).status,
+        }
+    }
+
+    /// approved withdraws waiting for the ethereum side, grouped by (token, destination)
+    pub fn burn_batch_hints() -> Vec<(TokenId, H160, Vec<T::Hash>)> {
+        let mut batches: Vec<(TokenId, H160, Vec<T::Hash>)> = Vec::new();
+        Self::approved_withdraws()
+            .into_iter()
+            .map(|id| <TransferMessages<T>>::get(id))
+            .for_each(|m| {
+                match batches
+                    .iter_mut()
+                    .find(|(token, to, _)| *token == m.token && *to == m.eth_address)
+                {
+                    Some(batch) => batch.2.push(m.message_id),
+                    None => batches.push((m.token, m.eth_address, vec![m.message_id])),
+                }
+            });
+        batches
+    }
+
+    /// distinct tokens the account has bridged, in order of first use
+    pub fn account_active_tokens(account: T::AccountId) -> Vec<TokenId> {
+        let mut tokens: Vec<TokenId> = Vec::new();
+        Self::account_transfers(account)
+            .iter()
+            .map(|id| <TransferMessages<T>>::get(id).token)
+            .for_each(|t| {
+                if !tokens.contains(&t) {
+                    tokens.push(t);
+                }
+            });
+        tokens
+    }
+
+    /// open proposals the validator hasn't voted for yet
+    pub fn proposals_awaiting(validator: T::AccountId) -> Vec<(ProposalId, Kind)> {
+        Self::open_transfers()
+            .into_iter()
+            .filter(|t| !<ValidatorVotes<T>>::get((t.transfer_id, validator.clone())))
+            .map(|t| (t.transfer_id, t.kind))
+            .collect()
+    }
+
+    /// up to max_items open proposals from the cursor id on with their message status,
+    /// along with pending volumes and limits
+    pub fn full_state_snapshot(
+        cursor: ProposalId,
+        max_items: u32,
+    ) -> BridgeSnapshot<T::Hash, T::Balance> {
+        let mut transfers: Vec<_> = Self::open_transfers()
+            .into_iter()
+            .filter(|t| t.transfer_id >= cursor)
+            .collect();
+        let next = transfers.get(max_items as usize).map(|t| t.transfer_id);
+        transfers.truncate(max_items as usize);
+        BridgeSnapshot {
+            transfers: transfers
+                .into_iter()
+                .map(|t| {
+                    let status = Self::proposal_status(&t);
+                    (t, status)
+                })
+                .collect(),
+            pending_burn: Self::pending_burn_count(),
+            pending_mint: Self::pending_mint_count(),
+            limits: Self::current_limits(),
+            next,
+        }
+    }
+
+    /// message of the proposal, None for an unknown id
+    pub fn proposal_message(
+        proposal_id: ProposalId,
+    ) -> Option<ProposalMessage<T::AccountId, T::Hash, T::Balance>> {
+        if !<BridgeTransfers<T>>::contains_key(proposal_id) {
+            return None;
+        }
+        let transfer = <BridgeTransfers<T>>::get(proposal_id);
+        let id = transfer.message_id;
+        Some(match transfer.kind {
+            Kind::Transfer => ProposalMessage::Transfer(<TransferMessages<T>>::get(id)),
+            Kind::Limits => ProposalMessage::Limits(<LimitMessages<T>>::get(id)),
+            Kind::Validator => ProposalMessage::Validator(<ValidatorHistory<T>>::get(id)),
+            Kind::Bridge => ProposalMessage::Bridge(<BridgeMessages<T>>::get(id)),
+        })
+    }
+
+    /// bridge config values that changed since genesis
+    pub fn config_diff() -> ConfigDiff<T::AccountId, T::Balance> {
+        let (limits, validators, quorum) = Self::genesis_snapshot();
+        let current_limits = Self::current_limits();
+        let current_validators = Self::validator_accounts();
+        let current_quorum = Self::quorum();
+        ConfigDiff {
+            limits: Some((limits, current_limits)).filter(|(g, c)| g != c),
+            validators: Some((validators, current_validators)).filter(|(g, c)| g != c),
+            quorum: Some((quorum, current_quorum)).filter(|(g, c)| g != c),
+        }
+    }
+
+    /// confirmed limit changes, newest first
+    /// limits, validators count and quorum as of the block, rebuilt from the
+    /// change histories. None for future blocks and blocks older than the retained history
+    pub fn config_at(block: T::BlockNumber) -> Option<BridgeConfigSnapshot<T::Balance>> {
+        if block > <system::Module<T>>::block_number() {
+            return None;
+        }
+        let (genesis_limits, genesis_validators, genesis_quorum) = Self::genesis_snapshot();
+        let limits = Self::value_at(
+            &Self::limit_history(),
+            MAX_LIMIT_HISTORY,
+            block,
+            genesis_limits,
+        )?;
+        let (validators_count, quorum) = Self::value_at(
+            &Self::validator_set_history(),
+            MAX_VALIDATOR_SET_HISTORY,
+            block,
+            (genesis_validators.len() as u32, genesis_quorum),
+        )?;
+        Some(BridgeConfigSnapshot {
+            limits,
+            validators_count,
+            quorum,
+        })
+    }
+
+    /// latest value recorded at or before the block, genesis if none was,
+    /// unless a full history may have dropped it
+    fn value_at<V: Clone>(
+        history: &[(T::BlockNumber, V)],
+        bound: usize,
+        block: T::BlockNumber,
+        genesis: V,
+    ) -> Option<V> {
+        match history.iter().rev().find(|(changed_at, _)| *changed_at <= block) {
+            Some((_, value)) => Some(value.clone()),
+            None if history.len() >= bound => None,
+            None => Some(genesis),
+        }
+    }
+
+    /// limits applied to each registered token
+    pub fn all_token_limits() -> Vec<(TokenId, Limits<T::Balance>)> {
+        <token::Module<T>>::tokens()
+            .into_iter()
+            .map(|t| (t.id, Self::limits_of(t.id)))
+            .collect()
+    }
+
+    /// limits of the token, the shared ones unless it has its own
+    pub fn limits_of(token_id: TokenId) -> Limits<T::Balance> {
+        Self::token_limits(token_id).unwrap_or_else(Self::current_limits)
+    }
+
+    pub fn limit_history_newest_first() -> Vec<(T::BlockNumber, Limits<T::Balance>)> {
+        let mut history = Self::limit_history();
+        history.reverse();
+        history
+    }
+
+    fn _confirm_transfer(validator: T::AccountId, message_id: T::Hash) -> Result<()> {
+        let id = <TransferId<T>>::get(message_id);
+
+        let is_approved = <TransferMessages<T>>::get(message_id).status == Status::Approved ||
+        <TransferMessages<T>>::get(message_id).status == Status::Confirmed;
+        ensure!(is_approved, "This transfer must be approved first.");
+
+        Self::update_status(message_id, Status::Confirmed, Kind::Transfer)?;
+        Self::reopen_for_burn_confirmation(message_id)?;
+        Self::_sign(validator.clone(), id)?;
+        // the burn is executed once the vote reaches quorum, nobody to slash
+        if <BridgeTransfers<T>>::get(id).open {
+            <ConfirmVoters<T>>::mutate(message_id, |v| v.push(validator));
+        }
+        Ok(())
+    }
+
+    /// whether the validator's confirmation would go through, checked before
+    /// anything is written since a failed item of a batch isn't reverted
+    fn can_confirm(validator: &T::AccountId, message_id: T::Hash) -> bool {
+        if !<TransferMessages<T>>::contains_key(message_id) {
+            return false;
+        }
+        let message = <TransferMessages<T>>::get(message_id);
+        let id = <TransferId<T>>::get(message_id);
+        let transfer = <BridgeTransfers<T>>::get(id);
+        match message.status {
+            // reopened for confirmation, votes start over
+            Status::Approved => message.action == Status::Withdraw && !transfer.open,
+            Status::Confirmed => {
+                transfer.open && !<ValidatorVotes<T>>::get((id, validator.clone()))
+            }
+            _ => false,
+        }
+    }
+
+    fn _sign(validator: T::AccountId, transfer_id: ProposalId) -> Result<()> {
+        let mut transfer = <BridgeTransfers<T>>::get(transfer_id);
+
+        let mut message = <TransferMessages<T>>::get(transfer.message_id);
+        let mut limit_message = <LimitMessages<T>>::get(transfer.message_id);
+        let mut validator_message = <ValidatorHistory<T>>::get(transfer.message_id);
+        let mut bridge_message = <BridgeMessages<T>>::get(transfer.message_id);
+        let voted = <ValidatorVotes<T>>::get((transfer_id, validator.clone()));
+        ensure!(!voted, "This validator has already voted.");
+        ensure!(transfer.open, "This transfer is not open");
+        Self::check_validator_active(&validator)?;
+        transfer.votes += 1;
+        // executing a validator change may replace the quorum
+        let quorum = Self::quorum();
+
+        if Self::votes_are_enough(transfer.votes) {
+            match message.status {
+                Status::Confirmed | Status::Canceled => (), // if burn is confirmed or canceled
+                _ => match transfer.kind {
+                    Kind::Transfer => message.status = Status::Approved,
+                    Kind::Limits => limit_message.status = Status::Approved,
+                    Kind::Validator => validator_message.status = Status::Approved,
+                    Kind::Bridge => bridge_message.status = Status::Approved,
+                },
+            }
+            match transfer.kind {
+                Kind::Transfer => Self::execute_transfer(message)?,
+                Kind::Limits => Self::_update_limits(limit_message)?,
+                Kind::Validator => Self::manage_validator_list(validator_message)?,
+                Kind::Bridge => Self::manage_bridge(bridge_message)?,
+            }
+            transfer.open = false;
+            Self::index_open_proposal(transfer_id, false);
+        } else {
+            match message.status {
+                Status::Confirmed | Status::Canceled => (),
+                _ => Self::set_pending(transfer_id, transfer.kind.clone())?,
+            };
+        }
+
+        <LastSeen<T>>::insert(&validator, <system::Module<T>>::block_number());
+        <ValidatorVotes<T>>::mutate((transfer_id, validator.clone()), |a| *a = true);
+        Self::deposit_event(RawEvent::ProposalVoted(
+            transfer_id,
+            validator,
+            transfer.votes as u32,
+            quorum,
+        ));
+        <BridgeTransfers<T>>::insert(transfer_id, transfer);
+
+        Ok(())
+    }
+
+    /// create the deposit message on the first signature and vote for it
+    fn _multi_signed_mint(
+        validator: T::AccountId,
+        chain_id: ChainId,
+        message_id: T::Hash,
+        from: H160,
+        to: T::AccountId,
+        token_id: TokenId,
+        amount: T::Balance,
+    ) -> Result<()> {
+        if !<TransferMessages<T>>::contains_key(message_id) {
+            let message = TransferMessage {
+                message_id,
+                chain_id,
+                eth_address: from,
+                substrate_address: to.clone(),
+                amount,
+                token: token_id,
+                status: Status::Deposit,
+                action: Status::Deposit,
+            };
+            <TransferMessages<T>>::insert(message_id, message);
+            <AccountTransfers<T>>::mutate(to, |v| v.push(message_id));
+            Self::get_transfer_id_checked(message_id, Kind::Transfer)?;
+        }
+
+        let transfer_id = <TransferId<T>>::get(message_id);
+        Self::_sign(validator, transfer_id)
+    }
+
+    /// a message id opened on one chain can't be signed for another
+    fn check_message_chain(message_id: T::Hash, chain_id: ChainId) -> Result<()> {
+        if <TransferMessages<T>>::contains_key(message_id) {
+            ensure!(
+                <TransferMessages<T>>::get(message_id).chain_id == chain_id,
+                "Message belongs to another chain"
+            );
+        }
+        Ok(())
+    }
+
+    /// start of the next day, when the daily volume limits reset
+    pub fn next_daily_reset() -> T::Moment {
+        let (_, today) = Self::get_day_pair();
+        (today + T::Moment::from(1)) * T::Moment::from(DAY)
+    }
 
     ///get (yesterday,today) pair
     fn get_day_pair() -> (T::Moment, T::Moment) {
@@ -400,15 +1602,39 @@ impl<T: Trait> Module<T> {
 
     ///execute actual mint
     fn deposit(message: TransferMessage<T::AccountId, T::Hash, T::Balance>) -> Result<()> {
-        Self::sub_pending_mint(message.clone())?;
+        ensure!(Self::token_transfers_enabled(message.token), "Token transfers disabled.");
+        if Self::require_fresh_price_for_mint() && !T::TokenPrice::has_fresh_price(message.token) {
+            Self::deposit_event(RawEvent::MintBlockedNoPrice(message.token));
+            fail!("No fresh price for the token");
+        }
+        Self::check_not_system_account(&message.substrate_address)?;
+        Self::check_mint_recipient(message.token, &message.substrate_address)?;
+        Self::check_compliance(&message.substrate_address, message.token, message.amount, Status::Deposit)?;
+        let daily_minted = Self::check_daily_mint_volume(message.token, &message.substrate_address, message.amount)?;
+        let pending_mint = Self::pending_mint_count()
+            .checked_sub(&message.amount)
+            .ok_or("Overflow subtracting to new pending mint volume")?;
         let to = message.substrate_address;
-        if !<DailyHolds<T>>::contains_key(&to) {
-            <DailyHolds<T>>::insert(to.clone(), (T::BlockNumber::from(0), message.message_id));
+        <token::Module<T>>::_mint(message.token, to.clone(), message.amount)?;
+
+        // nothing fails below, a failed deposit still counts as pending
+        <CurrentPendingMint<T>>::put(pending_mint);
+        let (yesterday, today) = Self::get_day_pair();
+        <DailyMints<T>>::remove((message.token, to.clone(), yesterday));
+        <DailyMints<T>>::insert((message.token, to.clone(), today), daily_minted);
+        if !<DailyHolds<T>>::contains_key((message.token, to.clone())) {
+            <DailyHolds<T>>::insert(
+                (message.token, to.clone()),
+                (<system::Module<T>>::block_number(), message.message_id),
+            );
         }
+        <ProcessedMessages<T>>::insert(message.message_id, true);
+        <EthAddressVolume<T>>::mutate(message.eth_address, |(inbound, _)| {
+            *inbound = inbound.saturating_add(message.amount)
+        });
+        <BlockDigest>::mutate(|d| d.confirmations += 1);
 
-        <token::Module<T>>::_mint(message.token, to, message.amount)?;
-
-        Self::deposit_event(RawEvent::MintedMessage(message.message_id, message.token));
+        Self::deposit_event(RawEvent::MintedMessage(message.message_id, message.chain_id, message.token));
         Self::update_status(message.message_id, Status::Confirmed, Kind::Transfer)
     }
 
@@ -419,9 +1645,12 @@ impl<T: Trait> Module<T> {
         let to = message.eth_address;
         let from = message.substrate_address.clone();
         Self::lock_for_burn(&message, from.clone())?;
+        <BlockDigest>::mutate(|d| d.approvals += 1);
         Self::deposit_event(RawEvent::ApprovedRelayMessage(
             message.message_id,
+            message.chain_id,
             message.token,
+            Self::token_eth_address(message.token),
             from,
             to,
             message.amount,
@@ -429,24 +1658,289 @@ impl<T: Trait> Module<T> {
         Self::update_status(message.message_id, Status::Approved, Kind::Transfer)
     }
     fn _cancel_transfer(message: TransferMessage<T::AccountId, T::Hash, T::Balance>) -> Result<()> {
-        <token::Module<T>>::unlock(message.token, &message.substrate_address, message.amount)?;
+        if message.action == Status::Withdraw {
+            Self::release_withdraw_funds(&message)?;
+        }
+        Self::slash_confirm_voters(message.message_id);
+        <BlockDigest>::mutate(|d| d.cancellations += 1);
         Self::update_status(message.message_id, Status::Canceled, Kind::Transfer)
     }
+    /// slash the validators who confirmed a burn which got canceled
+    fn slash_confirm_voters(message_id: T::Hash) {
+        let voters = <ConfirmVoters<T>>::take(message_id);
+        if !Self::slashing_enabled() {
+            return;
+        }
+        for validator in voters {
+            let free = <balances::Module<T> as Currency<T::AccountId>>::free_balance(&validator);
+            let amount = T::SlashFraction::get() * free;
+            let (slashed, _) = <balances::Module<T> as Currency<T::AccountId>>::slash(&validator, amount);
+            Self::deposit_event(RawEvent::ValidatorSlashed(validator, slashed.peek()));
+        }
+    }
+    /// whether the withdraw is neither burned nor canceled yet,
+    /// a confirm or cancel vote alone doesn't finish it
+    fn withdraw_in_flight(message: &TransferMessage<T::AccountId, T::Hash, T::Balance>) -> bool {
+        let transfer = <BridgeTransfers<T>>::get(<TransferId<T>>::get(message.message_id));
+        match message.status {
+            Status::Confirmed | Status::Canceled => transfer.open,
+            _ => true,
+        }
+    }
+    /// give back the pending volume or locked funds of a withdraw,
+    /// a canceled one holds what it held when the cancel vote started
+    fn release_withdraw_funds(message: &TransferMessage<T::AccountId, T::Hash, T::Balance>) -> Result<()> {
+        let status = match message.status {
+            Status::Canceled => Self::canceled_from(message.message_id).unwrap_or(Status::Approved),
+            _ => message.status.clone(),
+        };
+        match status {
+            Status::Pending => Self::sub_pending_burn(message.clone())?,
+            // approved, the ethereum response may be under vote
+            Status::Approved | Status::Confirmed => {
+                <token::Module<T>>::unlock(message.token, &message.substrate_address, message.amount)?
+            }
+            _ => (),
+        }
+        <CanceledFrom<T>>::remove(message.message_id);
+        Ok(())
+    }
+    /// cancel an unfinished withdraw, giving back its pending volume or
+    /// locked funds and its share of the owner's daily volume
+    fn release_withdraw(message: &TransferMessage<T::AccountId, T::Hash, T::Balance>) -> Result<()> {
+        let owner = message.substrate_address.clone();
+        let daily_volume = Self::daily_limits_by_account((message.token, owner.clone()))
+            .checked_sub(&message.amount)
+            .ok_or("Overflow updating daily limit")?;
+        Self::release_withdraw_funds(message)?;
+        <DailyLimits<T>>::insert((message.token, owner), daily_volume);
+
+        let transfer_id = <TransferId<T>>::get(message.message_id);
+        <BridgeTransfers<T>>::mutate(transfer_id, |t| t.open = false);
+        Self::index_open_proposal(transfer_id, false);
+        Self::update_status(message.message_id, Status::Canceled, Kind::Transfer)
+    }
+    fn cancel_reorged_mint(message: TransferMessage<T::AccountId, T::Hash, T::Balance>) -> Result<()> {
+        // a scheduled mint still counts as pending until it is executed
+        if message.status == Status::Pending || message.status == Status::Approved {
+            Self::sub_pending_mint(message.clone())?;
+        }
+        let transfer_id = <TransferId<T>>::get(message.message_id);
+        <BridgeTransfers<T>>::mutate(transfer_id, |t| t.open = false);
+        Self::index_open_proposal(transfer_id, false);
+        <BlockDigest>::mutate(|d| d.cancellations += 1);
+
+        Self::deposit_event(RawEvent::MintReorgCancelled(message.message_id, message.token));
+        Self::update_status(message.message_id, Status::Canceled, Kind::Transfer)
+    }
+    /// approve the deposit, minting it MintConfirmationDelay blocks later
+    fn schedule_mint(message: TransferMessage<T::AccountId, T::Hash, T::Balance>) -> Result<()> {
+        let transfer_id = <TransferId<T>>::get(message.message_id);
+        let execute_at = <system::Module<T>>::block_number() + T::MintConfirmationDelay::get();
+        <MintExecuteAt<T>>::insert(transfer_id, execute_at);
+        <MintsDueAt<T>>::mutate(execute_at, |ids| ids.push(transfer_id));
+        Self::deposit_event(RawEvent::MintScheduled(message.message_id, execute_at));
+        Self::update_status(message.message_id, Status::Approved, Kind::Transfer)
+    }
+    /// mint the scheduled deposits due at the block, skipping the canceled ones
+    /// and canceling the ones which can't be minted anymore
+    fn execute_due_mints(block_number: T::BlockNumber) {
+        for transfer_id in <MintsDueAt<T>>::take(block_number) {
+            <MintExecuteAt<T>>::remove(transfer_id);
+            let message = <TransferMessages<T>>::get(<BridgeTransfers<T>>::get(transfer_id).message_id);
+            if message.status != Status::Approved {
+                continue;
+            }
+            if let Err(e) = Self::deposit(message.clone()) {
+                debug::error!("Error executing scheduled mint {:?}: {:?}", transfer_id, e);
+                if let Err(e) = Self::sub_pending_mint(message.clone()) {
+                    debug::error!("Error canceling scheduled mint {:?}: {:?}", transfer_id, e);
+                }
+                <BlockDigest>::mutate(|d| d.cancellations += 1);
+                Self::deposit_event(RawEvent::ScheduledMintFailed(message.message_id, message.token));
+                let _ = Self::update_status(message.message_id, Status::Canceled, Kind::Transfer);
+            }
+        }
+    }
+    /// cancel mints which didn't reach quorum within PendingMintTtl
+    fn expire_pending_mints(block_number: T::BlockNumber) {
+        for transfer_id in <PendingMintExpiry<T>>::take(block_number) {
+            let mut transfer = <BridgeTransfers<T>>::get(transfer_id);
+            let message = <TransferMessages<T>>::get(transfer.message_id);
+            if !transfer.open || message.status != Status::Pending {
+                continue;
+            }
+            if let Err(e) = Self::sub_pending_mint(message.clone()) {
+                debug::error!("Error expiring pending mint {:?}: {:?}", transfer_id, e);
+                continue;
+            }
+            transfer.open = false;
+            <BridgeTransfers<T>>::insert(transfer_id, transfer);
+            Self::index_open_proposal(transfer_id, false);
+            <BlockDigest>::mutate(|d| d.cancellations += 1);
+            Self::deposit_event(RawEvent::PendingMintExpired(message.message_id, message.token));
+            let _ = Self::update_status(message.message_id, Status::Canceled, Kind::Transfer);
+        }
+    }
+    /// cancel withdraws which didn't reach quorum within TransferTtl,
+    /// their funds are only locked once approved so there is nothing to unlock
+    fn expire_transfers(block_number: T::BlockNumber) {
+        for transfer_id in <TransfersExpiringAt<T>>::take(block_number) {
+            <TransferExpiresAt<T>>::remove(transfer_id);
+            let mut transfer = <BridgeTransfers<T>>::get(transfer_id);
+            let message = <TransferMessages<T>>::get(transfer.message_id);
+            let awaits_quorum = message.status == Status::Withdraw || message.status == Status::Pending;
+            if !transfer.open || !awaits_quorum {
+                continue;
+            }
+            if message.status == Status::Pending {
+                if let Err(e) = Self::sub_pending_burn(message.clone()) {
+                    debug::error!("Error expiring transfer {:?}: {:?}", transfer_id, e);
+                    continue;
+                }
+            }
+            <DailyLimits<T>>::mutate((message.token, message.substrate_address.clone()), |a| {
+                *a = a.saturating_sub(message.amount)
+            });
+            transfer.open = false;
+            <BridgeTransfers<T>>::insert(transfer_id, transfer);
+            Self::index_open_proposal(transfer_id, false);
+            <BlockDigest>::mutate(|d| d.cancellations += 1);
+            Self::deposit_event(RawEvent::ExpiredMessage(message.message_id, message.token));
+            let _ = Self::update_status(message.message_id, Status::Canceled, Kind::Transfer);
+        }
+    }
     fn pause_the_bridge(message: BridgeMessage<T::AccountId, T::Hash>) -> Result<()> {
         <BridgeIsOperational>::mutate(|x| *x = false);
         Self::update_status(message.message_id, Status::Confirmed, Kind::Bridge)
     }
 
     fn resume_the_bridge(message: BridgeMessage<T::AccountId, T::Hash>) -> Result<()> {
+        ensure!(!Self::force_paused(), "Bridge is force-paused; requires force_resume.");
         <BridgeIsOperational>::mutate(|x| *x = true);
         Self::update_status(message.message_id, Status::Confirmed, Kind::Bridge)
     }
 
     fn _update_limits(message: LimitMessage<T::Hash, T::Balance>) -> Result<()> {
         Self::check_limits(&message.limits)?;
-        <CurrentLimits<T>>::put(message.limits);
+        match message.token {
+            Some(token_id) => <TokenLimits<T>>::insert(token_id, message.limits.clone()),
+            // temporary limits keep their window, the update takes over after it
+            None if Self::limits_before_temporary().is_some() => {
+                <LimitsBeforeTemporary<T>>::put(message.limits.clone())
+            }
+            None => Self::set_current_limits(message.limits.clone()),
+        }
+        Self::deposit_event(RawEvent::LimitsChanged(message.id, message.limits));
         Self::update_status(message.id, Status::Confirmed, Kind::Limits)
     }
+
+    /// replace current limits, keeping the replaced ones for a rollback
+    fn set_current_limits(limits: Limits<T::Balance>) {
+        <LimitHistory<T>>::mutate(|history| {
+            if history.len() >= MAX_LIMIT_HISTORY {
+                history.remove(0);
+            }
+            history.push((<system::Module<T>>::block_number(), limits.clone()));
+        });
+        <PreviousLimits<T>>::put(<CurrentLimits<T>>::get());
+        <CurrentLimits<T>>::put(limits);
+    }
+
+    /// switch to the scheduled temporary limits inside their window and back after it
+    fn apply_temporary_limits(now: T::BlockNumber) {
+        let (limits, from, until) = match Self::temporary_limits() {
+            Some(scheduled) => scheduled,
+            None => return,
+        };
+        if now >= until {
+            <TemporaryLimits<T>>::kill();
+            if let Some(previous) = <LimitsBeforeTemporary<T>>::take() {
+                Self::set_current_limits(previous);
+                Self::deposit_event(RawEvent::TemporaryLimitsExpired);
+            }
+        } else if now >= from && Self::limits_before_temporary().is_none() {
+            <LimitsBeforeTemporary<T>>::put(<CurrentLimits<T>>::get());
+            Self::set_current_limits(limits);
+            Self::deposit_event(RawEvent::TemporaryLimitsActive(until));
+        }
+    }
+
+    /// (burn, mint) volumes of open transfers waiting for quorum,
+    /// scheduled mints count as pending until they are executed
+    fn open_pending_volumes() -> Result<(T::Balance, T::Balance)> {
+        let mut burn = T::Balance::zero();
+        let mut mint = T::Balance::zero();
+        for id in 0..Self::bridge_transfers_count() {
+            let transfer = <BridgeTransfers<T>>::get(id);
+            let scheduled = <MintExecuteAt<T>>::contains_key(id);
+            if !(transfer.open || scheduled) || transfer.kind != Kind::Transfer {
+                continue;
+            }
+            let message = <TransferMessages<T>>::get(transfer.message_id);
+            let awaits_mint = scheduled && message.status == Status::Approved;
+            if message.status != Status::Pending && !awaits_mint {
+                continue;
+            }
+            match message.action {
+                Status::Withdraw => {
+                    burn = burn
+                        .checked_add(&message.amount)
+                        .ok_or("Overflow adding to pending burn volume")?
+                }
+                Status::Deposit => {
+                    mint = mint
+                        .checked_add(&message.amount)
+                        .ok_or("Overflow adding to pending mint volume")?
+                }
+                _ => (),
+            }
+        }
+        Ok((burn, mint))
+    }
+
+    /// internal accounting that doesn't match the open transfers
+    pub fn invariant_violations() -> Result<Vec<&'static str>> {
+        let mut violations = Vec::new();
+        let (burn, mint) = Self::open_pending_volumes()?;
+        if <CurrentPendingBurn<T>>::get() != burn {
+            violations.push("Pending burn counter differs from open withdraws");
+        }
+        if <CurrentPendingMint<T>>::get() != mint {
+            violations.push("Pending mint counter differs from open deposits");
+        }
+
+        // approved withdraws summed by (token, account), funds are locked until confirmed
+        let mut locks: Vec<(TokenId, T::AccountId, T::Balance)> = Vec::new();
+        for id in 0..Self::bridge_transfers_count() {
+            let transfer = <BridgeTransfers<T>>::get(id);
+            if transfer.kind != Kind::Transfer {
+                continue;
+            }
+            let message = <TransferMessages<T>>::get(transfer.message_id);
+            if message.action != Status::Withdraw || message.status != Status::Approved {
+                continue;
+            }
+            match locks
+                .iter_mut()
+                .find(|(token, account, _)| *token == message.token && *account == message.substrate_address)
+            {
+                Some((_, _, amount)) => {
+                    *amount = amount
+                        .checked_add(&message.amount)
+                        .ok_or("Overflow adding to locked volume")?
+                }
+                None => locks.push((message.token, message.substrate_address, message.amount)),
+            }
+        }
+        if locks
+            .into_iter()
+            .any(|(token, account, amount)| <token::Module<T>>::locked((token, account)) != amount)
+        {
+            violations.push("Locked funds differ from approved withdraws");
+        }
+        Ok(violations)
+    }
     fn add_pending_burn(message: TransferMessage<T::AccountId, T::Hash, T::Balance>) -> Result<()> {
         let current = <CurrentPendingBurn<T>>::get();
         let next = current
@@ -480,25 +1974,240 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    fn propose_validator_set(
+        validator: T::AccountId,
+        message_id: T::Hash,
+        quorum: u64,
+        accounts: Vec<T::AccountId>,
+        limits: Option<Limits<T::Balance>>,
+    ) -> Result<()> {
+        Self::check_validator(validator.clone())?;
+        Self::check_quorum(quorum)?;
+        Self::check_validator_set_size(accounts.len() as u32, quorum)?;
+        Self::check_distinct_validators(&accounts)?;
+        ensure!(Self::pending_validator_set().is_none(), "Validator set change already scheduled");
+
+        if !<ValidatorHistory<T>>::contains_key(message_id) {
+            let message = ValidatorMessage {
+                message_id,
+                quorum,
+                accounts,
+                action: Status::UpdateValidatorSet,
+                status: Status::UpdateValidatorSet,
+            };
+            <ValidatorHistory<T>>::insert(message_id, message);
+            <JointLimits<T>>::set(message_id, limits);
+            Self::get_transfer_id_checked(message_id, Kind::Validator)?;
+        }
+
+        let id = <TransferId<T>>::get(message_id);
+        Self::_sign(validator, id)
+    }
+
     /// update validators list
     fn manage_validator_list(info: ValidatorMessage<T::AccountId, T::Hash>) -> Result<()> {
-        let new_count = info.accounts.clone().len() as u32;
-        ensure!(
-            new_count < MAX_VALIDATORS,
-            "New validator list is exceeding allowed length."
-        );
+        if info.action == Status::UpdateQuorum {
+            Self::check_quorum_fits(info.quorum)?;
+        } else {
+            let new_count = info.accounts.clone().len() as u32;
+            ensure!(
+                new_count < MAX_VALIDATORS,
+                "New validator list is exceeding allowed length."
+            );
+            Self::check_quorum(info.quorum)?;
+            Self::check_validator_set_size(new_count, info.quorum)?;
+            Self::check_distinct_validators(&info.accounts)?;
+            if let Some(limits) = Self::joint_limits(info.message_id) {
+                Self::check_limits(&limits)?;
+            }
+        }
+
+        let delay = T::ValidatorChangeDelay::get();
+        if !delay.is_zero() {
+            // another proposal may have been scheduled since this one was opened
+            ensure!(Self::pending_validator_set().is_none(), "Validator set change already scheduled");
+            let effective_at = <system::Module<T>>::block_number() + delay;
+            Self::deposit_event(RawEvent::ValidatorChangeScheduled(info.message_id, effective_at));
+            Self::update_status(info.message_id, Status::Approved, Kind::Validator)?;
+            <PendingValidatorSet<T>>::put((info, effective_at));
+            return Ok(());
+        }
+        Self::apply_validator_change(info)
+    }
+
+    fn apply_validator_change(info: ValidatorMessage<T::AccountId, T::Hash>) -> Result<()> {
+        match info.action {
+            Status::UpdateQuorum => Self::apply_quorum(info),
+            _ => Self::apply_validator_set(info),
+        }
+    }
+
+    /// replace only the quorum, the validator set stays as is
+    fn apply_quorum(info: ValidatorMessage<T::AccountId, T::Hash>) -> Result<()> {
+        Self::check_quorum_fits(info.quorum)?;
+        let count = Self::validators_count();
+        <Quorum>::put(info.quorum);
+        <ValidatorSetHistory<T>>::mutate(|history| {
+            if history.len() >= MAX_VALIDATOR_SET_HISTORY {
+                history.remove(0);
+            }
+            history.push((<system::Module<T>>::block_number(), (count, info.quorum)));
+        });
+        Self::update_status(info.message_id, Status::Confirmed, Kind::Validator)
+    }
+
+    /// apply the scheduled validator set change once its block is reached
+    fn apply_pending_validator_set(now: T::BlockNumber) {
+        match Self::pending_validator_set() {
+            Some((info, effective_at)) if effective_at <= now => {
+                <PendingValidatorSet<T>>::kill();
+                let message_id = info.message_id;
+                match Self::apply_validator_change(info) {
+                    Ok(_) => Self::deposit_event(RawEvent::ValidatorChangeApplied(message_id)),
+                    Err(e) => debug::error!("Failed to apply validator set change: {:?}", e),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn cancel_pending_validator_set(message: BridgeMessage<T::AccountId, T::Hash>) -> Result<()> {
+        let (pending, _) = <PendingValidatorSet<T>>::take().ok_or("No validator set change scheduled")?;
+        Self::update_status(pending.message_id, Status::Canceled, Kind::Validator)?;
+        Self::deposit_event(RawEvent::ValidatorChangeCanceled(pending.message_id));
+        Self::update_status(message.message_id, Status::Confirmed, Kind::Bridge)
+    }
+
+    fn withdraw_limit_proposal_by_quorum(message: BridgeMessage<T::AccountId, T::Hash>) -> Result<()> {
+        let (limit_id, transfer_id) = <LimitProposalWithdrawals<T>>::take(message.message_id)
+            .ok_or("Unknown limit proposal withdrawal")?;
+        // the proposer may have withdrawn it already or the limits went through meanwhile
+        let still_open = <TransferId<T>>::contains_key(limit_id)
+            && <TransferId<T>>::get(limit_id) == transfer_id
+            && <BridgeTransfers<T>>::get(transfer_id).open;
+        if still_open {
+            Self::close_limit_proposal(limit_id, transfer_id)?;
+        }
+        Self::update_status(message.message_id, Status::Confirmed, Kind::Bridge)
+    }
+
+    fn close_limit_proposal(message_id: T::Hash, transfer_id: ProposalId) -> Result<()> {
+        let mut transfer = <BridgeTransfers<T>>::get(transfer_id);
+        transfer.open = false;
+        transfer.votes = 0;
+        Self::index_open_proposal(transfer_id, false);
+        for account in Self::validator_accounts() {
+            <ValidatorVotes<T>>::remove((transfer_id, account));
+        }
+        <BridgeTransfers<T>>::insert(transfer_id, transfer);
+        // the message stays as a record, freeing the transfer lets the same limits be proposed again
+        <TransferId<T>>::remove(message_id);
+        Self::update_status(message_id, Status::Canceled, Kind::Limits)?;
+        Self::deposit_event(RawEvent::LimitProposalWithdrawn(message_id));
+        Ok(())
+    }
+
+    fn apply_token_eth_address(message: BridgeMessage<T::AccountId, T::Hash>) -> Result<()> {
+        let (token_id, address) = <TokenAddressProposals<T>>::take(message.message_id)
+            .ok_or("Unknown token address proposal")?;
+        <TokenEthAddress>::insert(token_id, address);
+        Self::deposit_event(RawEvent::TokenAddressUpdated(token_id, address));
+        Self::update_status(message.message_id, Status::Confirmed, Kind::Bridge)
+    }
+
+    fn apply_validator_set(info: ValidatorMessage<T::AccountId, T::Hash>) -> Result<()> {
+        let joint_limits = Self::joint_limits(info.message_id);
+        if let Some(limits) = &joint_limits {
+            Self::check_limits(limits)?;
+        }
+        let new_count = info.accounts.len() as u32;
         <Quorum>::put(info.quorum);
         <ValidatorsCount>::put(new_count);
-        info.accounts
-            .clone()
-            .iter()
-            .for_each(|v| <Validators<T>>::insert(v, true));
+        <ValidatorSetHistory<T>>::mutate(|history| {
+            if history.len() >= MAX_VALIDATOR_SET_HISTORY {
+                history.remove(0);
+            }
+            history.push((<system::Module<T>>::block_number(), (new_count, info.quorum)));
+        });
+        for v in Self::validator_accounts().into_iter().filter(|v| !info.accounts.contains(v)) {
+            Self::remove_validator(&v)?;
+        }
+        <ValidatorAccounts<T>>::put(info.accounts.clone());
+        if new_count == T::MinValidators::get() {
+            Self::deposit_event(RawEvent::ValidatorSetAtMinimum(new_count));
+        }
+        // quorum must stay an honest majority of the set
+        if info.quorum * 2 <= u64::from(new_count) {
+            Self::deposit_event(RawEvent::ValidatorSetBelowSafe(new_count, info.quorum));
+        }
+        let now = <system::Module<T>>::block_number();
+        info.accounts.clone().iter().for_each(|v| {
+            if !<Validators<T>>::contains_key(v) {
+                <ValidatorActiveFrom<T>>::insert(v, now);
+            }
+            <Validators<T>>::insert(v, true)
+        });
+        if let Some(limits) = joint_limits {
+            <JointLimits<T>>::remove(info.message_id);
+            Self::set_current_limits(limits);
+        }
         Self::update_status(info.message_id, Status::Confirmed, Kind::Validator)
     }
 
-    /// check votes validity
+    /// revoke trust of a validator dropped from the set
+    /// and withdraw its votes from open proposals
+    fn remove_validator(validator: &T::AccountId) -> Result<()> {
+        <Validators<T>>::remove(validator);
+        <ValidatorActiveFrom<T>>::remove(validator);
+        for id in Self::open_proposals() {
+            if !<ValidatorVotes<T>>::get((id, validator.clone())) {
+                continue;
+            }
+            let mut transfer = <BridgeTransfers<T>>::get(id);
+            transfer.votes = transfer.votes.saturating_sub(1);
+            <ValidatorVotes<T>>::remove((id, validator.clone()));
+            if transfer.votes == 0 && transfer.kind == Kind::Transfer {
+                Self::unset_pending(transfer.message_id)?;
+            }
+            <BridgeTransfers<T>>::insert(id, transfer);
+        }
+        Ok(())
+    }
+
+    /// check votes validity: a proposal executes as soon as its votes
+    /// reach the quorum, reaching it exactly is enough
     fn votes_are_enough(votes: MemberId) -> bool {
-        votes as f64 / f64::from(Self::validators_count()) >= 0.51
+        votes >= Self::quorum()
+    }
+
+    /// zero quorum would execute every proposal without a single vote
+    fn check_quorum(quorum: u64) -> Result<()> {
+        ensure!(quorum > 0, "Quorum can't be zero");
+        Ok(())
+    }
+
+    /// a set smaller than the quorum could never approve anything again
+    fn check_validator_set_size(count: u32, quorum: u64) -> Result<()> {
+        ensure!(count >= 1 && u64::from(count) >= quorum, "Validator set too small for quorum");
+        Ok(())
+    }
+
+    /// a repeated account would be counted as several validators
+    fn check_distinct_validators(accounts: &[T::AccountId]) -> Result<()> {
+        for (i, account) in accounts.iter().enumerate() {
+            ensure!(!accounts[..i].contains(account), "Duplicate validator in list");
+        }
+        Ok(())
+    }
+
+    /// a quorum above the validator count could never be reached
+    fn check_quorum_fits(quorum: u64) -> Result<()> {
+        Self::check_quorum(quorum)?;
+        ensure!(
+            quorum <= u64::from(Self::validators_count()),
+            "Quorum exceeds the number of validators"
+        );
+        Ok(())
     }
 
     /// lock funds after set_transfer call
@@ -515,14 +2224,24 @@ impl<T: Trait> Module<T> {
         let message = <TransferMessages<T>>::get(message_id);
         let from = message.substrate_address.clone();
         let to = message.eth_address;
+        let daily_volume = Self::daily_limits_by_account((message.token, from.clone()))
+            .checked_sub(&message.amount)
+            .ok_or("Overflow updating daily limit")?;
 
         <token::Module<T>>::unlock(message.token, &from, message.amount)?;
         <token::Module<T>>::_burn(message.token, from.clone(), message.amount)?;
-        <DailyLimits<T>>::mutate((message.token, from.clone()), |a| *a -= message.amount);
+        <ConfirmVoters<T>>::remove(message_id);
+        <DailyLimits<T>>::insert((message.token, from.clone()), daily_volume);
+        <EthAddressVolume<T>>::mutate(to, |(_, outbound)| {
+            *outbound = outbound.saturating_add(message.amount)
+        });
+        <BlockDigest>::mutate(|d| d.confirmations += 1);
 
         Self::deposit_event(RawEvent::BurnedMessage(
             message_id,
+            message.chain_id,
             message.token,
+            Self::token_eth_address(message.token),
             from,
             to,
             message.amount,
@@ -533,6 +2252,7 @@ impl<T: Trait> Module<T> {
     fn execute_transfer(message: TransferMessage<T::AccountId, T::Hash, T::Balance>) -> Result<()> {
         match message.action {
             Status::Deposit => match message.status {
+                Status::Approved if !T::MintConfirmationDelay::get().is_zero() => Self::schedule_mint(message),
                 Status::Approved => Self::deposit(message),
                 Status::Canceled => Self::_cancel_transfer(message),
                 _ => Err("Tried to deposit with non-supported status"),
@@ -557,6 +2277,18 @@ impl<T: Trait> Module<T> {
                 Status::Approved => Self::resume_the_bridge(message),
                 _ => Err("Tried to resume the bridge with non-supported status"),
             },
+            Status::CancelValidatorChange => match message.status {
+                Status::Approved => Self::cancel_pending_validator_set(message),
+                _ => Err("Tried to cancel validator change with non-supported status"),
+            },
+            Status::UpdateTokenAddress => match message.status {
+                Status::Approved => Self::apply_token_eth_address(message),
+                _ => Err("Tried to update token address with non-supported status"),
+            },
+            Status::WithdrawLimitProposal => match message.status {
+                Status::Approved => Self::withdraw_limit_proposal_by_quorum(message),
+                _ => Err("Tried to withdraw limit proposal with non-supported status"),
+            },
             _ => Err("Tried to manage bridge with non-supported status"),
         }
     }
@@ -572,30 +2304,61 @@ impl<T: Trait> Module<T> {
         let new_bridge_transfers_count = bridge_transfers_count
             .checked_add(1)
             .ok_or("Overflow adding a new bridge transfer")?;
+        if kind == Kind::Transfer {
+            <BlockDigest>::mutate(|d| d.transfers_opened += 1);
+        }
         let transfer = BridgeTransfer {
             transfer_id,
             message_id: transfer_hash,
             open: true,
             votes: 0,
-            kind,
+            kind: kind.clone(),
         };
 
         <BridgeTransfers<T>>::insert(transfer_id, transfer);
         <BridgeTransfersCount>::mutate(|count| *count = new_bridge_transfers_count);
+        Self::index_open_proposal(transfer_id, true);
         <TransferId<T>>::insert(transfer_hash, transfer_id);
         <MessageId<T>>::insert(transfer_id, transfer_hash);
 
+        Self::deposit_event(RawEvent::TransferOpened(transfer_id, transfer_hash, kind));
         Ok(())
     }
 
+    /// keep OpenProposals in step with the open flag of the transfer
+    fn index_open_proposal(transfer_id: ProposalId, open: bool) {
+        <OpenProposals>::mutate(|ids| {
+            ids.retain(|id| *id != transfer_id);
+            if open {
+                ids.push(transfer_id);
+            }
+        });
+    }
+
+    fn index_approved_withdraw(message_id: T::Hash, approved: bool) {
+        <ApprovedWithdraws<T>>::mutate(|ids| {
+            ids.retain(|id| *id != message_id);
+            if approved {
+                ids.push(message_id);
+            }
+        });
+    }
+
     fn set_pending(transfer_id: ProposalId, kind: Kind) -> Result<()> {
         let message_id = <MessageId<T>>::get(transfer_id);
         match kind {
             Kind::Transfer => {
                 let message = <TransferMessages<T>>::get(message_id);
+                // the volume is counted once, by the vote opening the transfer
+                if message.status == Status::Pending {
+                    return Ok(());
+                }
                 match message.action {
                     Status::Withdraw => Self::add_pending_burn(message)?,
-                    Status::Deposit => Self::add_pending_mint(message)?,
+                    Status::Deposit => {
+                        Self::schedule_mint_expiry(transfer_id);
+                        Self::add_pending_mint(message)?
+                    }
                     _ => (),
                 }
             }
@@ -604,10 +2367,47 @@ impl<T: Trait> Module<T> {
         Self::update_status(message_id, Status::Pending, kind)
     }
 
+    /// undo set_pending once the last vote of a transfer is revoked
+    fn unset_pending(message_id: T::Hash) -> Result<()> {
+        let message = <TransferMessages<T>>::get(message_id);
+        if message.status != Status::Pending {
+            return Ok(());
+        }
+        match message.action {
+            Status::Withdraw => Self::sub_pending_burn(message.clone())?,
+            Status::Deposit => Self::sub_pending_mint(message.clone())?,
+            _ => (),
+        }
+        Self::update_status(message_id, message.action, Kind::Transfer)
+    }
+
+    fn schedule_mint_expiry(transfer_id: ProposalId) {
+        let ttl = T::PendingMintTtl::get();
+        if ttl.is_zero() {
+            return;
+        }
+        let expires_at = <system::Module<T>>::block_number() + ttl;
+        <PendingMintExpiry<T>>::mutate(expires_at, |ids| ids.push(transfer_id));
+    }
+
+    fn schedule_transfer_expiry(transfer_id: ProposalId) {
+        let ttl = T::TransferTtl::get();
+        if ttl.is_zero() {
+            return;
+        }
+        let expires_at = <system::Module<T>>::block_number() + ttl;
+        <TransferExpiresAt<T>>::insert(transfer_id, expires_at);
+        <TransfersExpiringAt<T>>::mutate(expires_at, |ids| ids.push(transfer_id));
+    }
+
     fn update_status(id: T::Hash, status: Status, kind: Kind) -> Result<()> {
         match kind {
             Kind::Transfer => {
                 let mut message = <TransferMessages<T>>::get(id);
+                let approved = status == Status::Approved;
+                if message.action == Status::Withdraw && (approved || message.status == Status::Approved) {
+                    Self::index_approved_withdraw(id, approved);
+                }
                 message.status = status;
                 <TransferMessages<T>>::insert(id, message);
             }
@@ -641,6 +2441,7 @@ impl<T: Trait> Module<T> {
             transfer.votes = 0;
             transfer.open = true;
             <BridgeTransfers<T>>::insert(transfer_id, transfer);
+            Self::index_open_proposal(transfer_id, true);
             let validators = <ValidatorAccounts<T>>::get();
             validators
                 .iter()
@@ -655,116 +2456,410 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    fn check_validator_active(validator: &T::AccountId) -> Result<()> {
+        if <ValidatorActiveFrom<T>>::contains_key(validator) {
+            let active_from =
+                Self::validator_active_from(validator) + T::ValidatorActivationDelay::get();
+            ensure!(
+                <system::Module<T>>::block_number() >= active_from,
+                "Validator is not active yet"
+            );
+        }
+        Ok(())
+    }
+
+    fn check_validators_ready() -> Result<()> {
+        let window = T::ValidatorActivityWindow::get();
+        if window == T::BlockNumber::from(0) {
+            return Ok(());
+        }
+        let now = <system::Module<T>>::block_number();
+        let active = <ValidatorAccounts<T>>::get()
+            .iter()
+            .filter_map(|v| Self::last_seen(v))
+            .filter(|seen| now <= *seen + window)
+            .count() as u64;
+        ensure!(
+            active >= Self::quorum(),
+            "Bridge not ready — insufficient active validators."
+        );
+        Ok(())
+    }
+
+    fn check_not_system_account(account: &T::AccountId) -> Result<()> {
+        let is_system = Self::reserve_account().as_ref() == Some(account)
+            || Self::fee_collector().as_ref() == Some(account);
+        ensure!(!is_system, "Cannot bridge to/from a system account.");
+        Ok(())
+    }
+
+    fn check_mint_recipient(token_id: TokenId, account: &T::AccountId) -> Result<()> {
+        if Self::token_is_permissioned(token_id) {
+            ensure!(
+                <MintRecipientAllowlist<T>>::get((token_id, account.clone())),
+                "Mint recipient is not allowlisted for this token"
+            );
+        }
+        Ok(())
+    }
+
+    fn check_compliance(
+        account: &T::AccountId,
+        token_id: TokenId,
+        amount: T::Balance,
+        direction: Status,
+    ) -> Result<()> {
+        ensure!(
+            T::ComplianceCheck::verify(account, token_id, amount, direction),
+            "Transfer rejected by compliance check"
+        );
+        Ok(())
+    }
+
     fn check_daily_account_volume(
         token_id: TokenId,
         account: T::AccountId,
         amount: T::Balance,
     ) -> Result<()> {
-        let cur_pending = <DailyLimits<T>>::get((token_id, &account));
-        let cur_pending_account_limit = <CurrentLimits<T>>::get().day_max_limit_for_one_address;
-        let can_burn = cur_pending + amount < cur_pending_account_limit;
-
+        if !Self::daily_limits_enforced() {
+            return Ok(());
+        }
         //store current day (like 18768)
         let today = Self::get_day_pair().1;
-        let user_blocked = <DailyBlocked<T>>::get((token_id, today))
-            .iter()
-            .any(|a| *a == account);
-
-        if !can_burn {
-            <DailyBlocked<T>>::mutate((token_id, today), |v| {
-                if !v.contains(&account) {
-                    v.push(account.clone());
-                    let now = <timestamp::Module<T>>::get();
-                    let hash = (now.clone(), account.clone())
-                        .using_encoded(<T as system::Trait>::Hashing::hash);
-                    Self::deposit_event(RawEvent::AccountPausedMessage(
-                        hash, account, now, token_id,
-                    ))
-                }
-            });
+        let would_exceed = Self::would_exceed_daily(token_id, &account, amount);
+
+        if Self::daily_volume_exceeded(token_id, &account, amount) {
+            Self::block_for_today(token_id, account, today);
         }
         ensure!(
-            can_burn && !user_blocked,
+            !would_exceed,
             "Transfer declined, user blocked due to daily volume limit."
         );
 
         Ok(())
     }
-    fn check_amount(amount: T::Balance) -> Result<()> {
-        let max = <CurrentLimits<T>>::get().max_tx_value;
-        let min = <CurrentLimits<T>>::get().min_tx_value;
 
+    /// mint volume of the account including the amount, blocking the account above the daily limit
+    fn check_daily_mint_volume(
+        token_id: TokenId,
+        account: &T::AccountId,
+        amount: T::Balance,
+    ) -> Result<T::Balance> {
+        let minted = Self::daily_mint_volume(token_id, account, amount)?;
+        if Self::daily_mint_exceeded(token_id, minted) {
+            Self::block_for_today(token_id, account.clone(), Self::get_day_pair().1);
+            fail!("Mint declined, user blocked due to daily volume limit.");
+        }
+        Ok(minted)
+    }
+
+    fn daily_mint_volume(token_id: TokenId, account: &T::AccountId, amount: T::Balance) -> Result<T::Balance> {
+        Self::daily_mints_by_account((token_id, account.clone(), Self::get_day_pair().1))
+            .checked_add(&amount)
+            .ok_or("Overflow updating daily mint volume")
+    }
+
+    fn daily_mint_exceeded(token_id: TokenId, minted: T::Balance) -> bool {
+        Self::daily_limits_enforced() && minted >= Self::limits_of(token_id).day_max_limit_for_one_address
+    }
+
+    /// checks of deposit without its events and blocking,
+    /// `earlier` is the volume minted to the account before it in the same call
+    fn check_deposit(token_id: TokenId, to: &T::AccountId, amount: T::Balance, earlier: T::Balance) -> Result<()> {
+        ensure!(Self::token_transfers_enabled(token_id), "Token transfers disabled.");
         ensure!(
-            amount > min,
-            "Invalid amount for transaction. Reached minimum limit."
+            !Self::require_fresh_price_for_mint() || T::TokenPrice::has_fresh_price(token_id),
+            "No fresh price for the token"
         );
+        Self::check_not_system_account(to)?;
+        Self::check_mint_recipient(token_id, to)?;
+        Self::check_compliance(to, token_id, amount, Status::Deposit)?;
+        let total = earlier.checked_add(&amount).ok_or("Overflow updating daily mint volume")?;
+        let minted = Self::daily_mint_volume(token_id, to, total)?;
         ensure!(
-            amount < max,
-            "Invalid amount for transaction. Reached maximum limit."
+            !Self::daily_mint_exceeded(token_id, minted),
+            "Mint declined, user blocked due to daily volume limit."
         );
         Ok(())
     }
-    //open transactions check
-    fn check_pending_burn(amount: T::Balance) -> Result<()> {
-        let new_pending_volume = <CurrentPendingBurn<T>>::get()
-            .checked_add(&amount)
-            .ok_or("Overflow adding to new pending burn volume")?;
-        let can_burn = new_pending_volume < <CurrentLimits<T>>::get().max_pending_tx_limit;
-        ensure!(can_burn, "Too many pending burn transactions.");
+
+    fn block_for_today(token_id: TokenId, account: T::AccountId, today: T::Moment) {
+        <DailyBlocked<T>>::mutate((token_id, today), |v| {
+            if !v.contains(&account) {
+                v.push(account.clone());
+                let now = <timestamp::Module<T>>::get();
+                let hash = (now.clone(), account.clone())
+                    .using_encoded(<T as system::Trait>::Hashing::hash);
+                Self::deposit_event(RawEvent::AccountPausedMessage(
+                    hash, account, now, token_id,
+                ))
+            }
+        });
+    }
+    /// create the withdraw message and its transfer, emitting RelayMessage
+    fn _set_transfer(
+        from: T::AccountId,
+        chain_id: ChainId,
+        to: H160,
+        token_id: TokenId,
+        amount: T::Balance,
+        allow_contract: bool,
+        transfer_hash: T::Hash,
+        count_rejections: bool,
+    ) -> Result<()> {
+        if count_rejections {
+            Self::count_rejection(&from, to, token_id, amount);
+        }
+        let daily_volume = Self::check_transfer(&from, to, token_id, amount)?;
+        Self::check_daily_account_volume(token_id, from.clone(), amount)?;
+
+        let message = TransferMessage {
+            message_id: transfer_hash,
+            chain_id,
+            eth_address: to,
+            substrate_address: from.clone(),
+            amount,
+            token: token_id,
+            status: Status::Withdraw,
+            action: Status::Withdraw,
+        };
+        let to_contract = Self::is_contract_destination(to);
+        ensure!(
+            !to_contract || allow_contract,
+            "Destination is a contract, set allow_contract to transfer anyway"
+        );
+
+        Self::get_transfer_id_checked(transfer_hash, Kind::Transfer)?;
+        if to_contract {
+            Self::deposit_event(RawEvent::ContractDestinationTransfer(transfer_hash, to));
+        }
+        Self::deposit_event(RawEvent::RelayMessage(transfer_hash, chain_id));
+
+        <DailyLimits<T>>::insert((token_id, from.clone()), daily_volume);
+        <AccountTransfers<T>>::mutate(from, |v| v.push(transfer_hash));
+        <TransferMessages<T>>::insert(transfer_hash, message);
+        Self::schedule_transfer_expiry(<TransferId<T>>::get(transfer_hash));
         Ok(())
     }
 
-    fn check_pending_mint(amount: T::Balance) -> Result<()> {
-        let new_pending_volume = <CurrentPendingMint<T>>::get()
+    /// checks of a withdraw that don't write storage, returns the new daily volume
+    fn check_transfer(
+        from: &T::AccountId,
+        to: H160,
+        token_id: TokenId,
+        amount: T::Balance,
+    ) -> Result<T::Balance> {
+        ensure!(Self::bridge_is_operational(), "Bridge is not operational");
+        Self::check_validators_ready()?;
+        ensure!(Self::token_transfers_enabled(token_id), "Token transfers disabled.");
+        Self::check_not_system_account(from)?;
+        ensure!(!to.is_zero(), "Invalid destination address");
+        Self::check_compliance(from, token_id, amount, Status::Withdraw)?;
+
+        Self::check_amount(token_id, amount)?;
+        Self::check_pending_burn(from, token_id, amount)?;
+        Self::daily_limits_by_account((token_id, from.clone()))
             .checked_add(&amount)
-            .ok_or("Overflow adding to new pending mint volume")?;
-        let can_burn = new_pending_volume < <CurrentLimits<T>>::get().max_pending_tx_limit;
-        ensure!(can_burn, "Too many pending mint transactions.");
-        Ok(())
+            .ok_or("Overflow updating daily limit")
     }
 
-    fn check_limits(limits: &Limits<T::Balance>) -> Result<()> {
-        let max = T::Balance::max_value();
-        let min = T::Balance::min_value();
-        let passed = limits
-            .into_array()
+    /// whether the amount would be declined by the daily volume limits,
+    /// unlike check_daily_account_volume it never blocks the account
+    pub fn would_exceed_daily(token_id: TokenId, account: &T::AccountId, amount: T::Balance) -> bool {
+        if !Self::daily_limits_enforced() {
+            return false;
+        }
+        let today = Self::get_day_pair().1;
+        let user_blocked = <DailyBlocked<T>>::get((token_id, today))
             .iter()
-            .fold((true, true), |acc, l| match acc {
-                (true, true) => (l < &max, l > &min),
-                (true, false) => (l < &max, false),
+            .any(|a| a == account);
+        user_blocked || Self::daily_volume_exceeded(token_id, account, amount)
+    }
+
+    /// outcome of a set_transfer without allow_contract, in the order the checks run
+    pub fn simulate_transfer(
+        account: &T::AccountId,
+        to: H160,
+        token_id: TokenId,
+        amount: T::Balance,
+    ) -> SimulatedOutcome {
+        let limits = Self::limits_of(token_id);
+        if !Self::bridge_is_operational() {
+            SimulatedOutcome::BridgeNotOperational
+        } else if Self::check_validators_ready().is_err() {
+            SimulatedOutcome::ValidatorsNotReady
+        } else if !Self::token_transfers_enabled(token_id) {
+            SimulatedOutcome::TokenTransfersDisabled
+        } else if Self::check_not_system_account(account).is_err() {
+            SimulatedOutcome::SystemAccount
+        } else if to.is_zero() {
+            SimulatedOutcome::InvalidDestination
+        } else if Self::check_compliance(account, token_id, amount, Status::Withdraw).is_err() {
+            SimulatedOutcome::ComplianceRejected
+        } else if amount < limits.min_tx_value {
+            SimulatedOutcome::BelowMinimum
+        } else if amount > limits.max_tx_value {
+            SimulatedOutcome::AboveMaximum
+        } else if Self::check_pending_burn(account, token_id, amount).is_err() {
+            SimulatedOutcome::PendingLimitReached
+        } else if Self::would_exceed_daily(token_id, account, amount) {
+            SimulatedOutcome::DailyLimitReached
+        } else if Self::is_contract_destination(to) {
+            SimulatedOutcome::ContractDestination
+        } else {
+            SimulatedOutcome::Accepted
+        }
+    }
+
+    /// count the withdraw if the daily or pending limits are about to decline it
+    fn count_rejection(account: &T::AccountId, to: H160, token_id: TokenId, amount: T::Balance) {
+        let reason = match Self::simulate_transfer(account, to, token_id, amount) {
+            SimulatedOutcome::DailyLimitReached => RejectionReason::DailyLimit,
+            SimulatedOutcome::PendingLimitReached => RejectionReason::PendingCapacity,
+            _ => return,
+        };
+        <RejectionsByReason>::mutate(&reason, |count| *count = count.saturating_add(1));
+        Self::deposit_event(RawEvent::TransferRejected(account.clone(), token_id, reason));
+    }
+
+    /// daily limits are skipped while the chain warms up after genesis
+    fn daily_limits_enforced() -> bool {
+        <system::Module<T>>::block_number() >= T::DailyLimitGraceBlocks::get()
+    }
+
+    fn daily_volume_exceeded(token_id: TokenId, account: &T::AccountId, amount: T::Balance) -> bool {
+        let cur_pending = <DailyLimits<T>>::get((token_id, account));
+        let cur_pending_account_limit = Self::limits_of(token_id).day_max_limit_for_one_address;
+        cur_pending
+            .checked_add(&amount)
+            .map_or(true, |volume| volume >= cur_pending_account_limit)
+    }
+
+    fn check_amount(token_id: TokenId, amount: T::Balance) -> Result<()> {
+        let limits = Self::limits_of(token_id);
+        let max = limits.max_tx_value;
+        let min = limits.min_tx_value;
+
+        // both limits are inclusive
+        ensure!(
+            amount >= min,
+            "Invalid amount for transaction. Below the minimum limit."
+        );
+        ensure!(
+            amount <= max,
+            "Invalid amount for transaction. Above the maximum limit."
+        );
+        Ok(())
+    }
+    //open transactions check
+    fn check_pending_burn(account: &T::AccountId, token_id: TokenId, amount: T::Balance) -> Result<()> {
+        if Self::is_priority_account(account) {
+            return Ok(());
+        }
+        let new_pending_volume = <CurrentPendingBurn<T>>::get()
+            .checked_add(&amount)
+            .ok_or("Overflow adding to new pending burn volume")?;
+        let can_burn = new_pending_volume < Self::limits_of(token_id).max_pending_tx_limit;
+        ensure!(can_burn, "Too many pending burn transactions.");
+        Ok(())
+    }
+
+    fn check_pending_mint(account: &T::AccountId, token_id: TokenId, amount: T::Balance) -> Result<()> {
+        if Self::is_priority_account(account) {
+            return Ok(());
+        }
+        let new_pending_volume = <CurrentPendingMint<T>>::get()
+            .checked_add(&amount)
+            .ok_or("Overflow adding to new pending mint volume")?;
+        let can_burn = new_pending_volume < Self::limits_of(token_id).max_pending_tx_limit;
+        ensure!(can_burn, "Too many pending mint transactions.");
+        Ok(())
+    }
+
+    fn check_limits(limits: &Limits<T::Balance>) -> Result<()> {
+        let max = T::Balance::max_value();
+        let min = T::Balance::min_value();
+        let passed = limits
+            .into_array()
+            .iter()
+            .fold((true, true), |acc, l| match acc {
+                (true, true) => (l < &max, l > &min),
+                (true, false) => (l < &max, false),
                 (false, true) => (false, l > &min),
                 (_, _) => acc,
             });
         ensure!(passed.0, "Overflow setting limit");
         ensure!(passed.1, "Underflow setting limit");
+        ensure!(
+            limits.min_tx_value < limits.max_tx_value,
+            "Minimum transaction value must be below maximum"
+        );
+        ensure!(
+            limits.day_max_limit_for_one_address <= limits.day_max_limit,
+            "Daily limit for one address exceeds daily limit"
+        );
         Ok(())
     }
 
     fn check_daily_holds(
         message: TransferMessage<T::AccountId, T::Hash, T::Balance>,
     ) -> Result<()> {
+        if !Self::first_day_hold_enabled(message.token) {
+            return Ok(());
+        }
         let from = message.substrate_address;
-        let first_tx = <DailyHolds<T>>::get(from.clone());
+        if <HoldBypassBonds<T>>::contains_key((message.token, from.clone())) {
+            return Ok(());
+        }
+        let first_tx = <DailyHolds<T>>::get((message.token, from.clone()));
         let daily_hold = T::BlockNumber::from(DAY_IN_BLOCKS);
-        let day_passed = first_tx.0 + daily_hold < T::BlockNumber::from(0);
+        let day_passed = first_tx.0 + daily_hold <= <system::Module<T>>::block_number();
 
         if !day_passed {
             let account_balance = <token::Module<T>>::balance_of((message.token, from));
-            // 75% of potentially really big numbers
+            // percent of potentially really big numbers
+            let percent = T::Balance::from(T::FirstDayWithdrawLimitPercent::get());
             let allowed_amount = account_balance
                 .checked_div(&T::Balance::from(100))
-                .expect("Failed to calculate allowed withdraw amount")
-                .checked_mul(&T::Balance::from(75))
-                .expect("Failed to calculate allowed withdraw amount");
+                .and_then(|b| b.checked_mul(&percent))
+                .ok_or("Failed to calculate allowed withdraw amount")?;
 
             if message.amount > allowed_amount {
                 Self::update_status(message.message_id, Status::Canceled, Kind::Transfer)?;
-                fail!("Cannot withdraw more that 75% of first day deposit.");
+                fail!("Cannot withdraw more than the allowed share of first day deposit.");
             }
         }
 
         Ok(())
     }
+
+    /// holds used to be recorded per account only, by deposits: move the
+    /// legacy entry of every account deposited to under every known token
+    fn migrate_daily_holds() {
+        for transfer_id in 0..<BridgeTransfersCount>::get() {
+            let message_id = <MessageId<T>>::get(transfer_id);
+            if !<TransferMessages<T>>::contains_key(message_id) {
+                continue;
+            }
+            let message = <TransferMessages<T>>::get(message_id);
+            if message.action == Status::Deposit {
+                Self::migrate_daily_hold(&message.substrate_address);
+            }
+        }
+    }
+
+    fn migrate_daily_hold(account: &T::AccountId) {
+        let hash = account.using_encoded(sp_io::hashing::blake2_256);
+        let legacy: Option<(T::BlockNumber, T::Hash)> =
+            take_storage_value(b"Bridge", b"DailyHolds", &hash);
+        if let Some(hold) = legacy {
+            <token::Module<T>>::tokens().iter().for_each(|t| {
+                if !<DailyHolds<T>>::contains_key((t.id, account.clone())) {
+                    <DailyHolds<T>>::insert((t.id, account.clone()), hold.clone());
+                }
+            });
+        }
+    }
 }
 
 /// tests for this module
@@ -774,9 +2869,9 @@ mod tests {
     //TODO: fix limits after adding them into config
     use crate::types::Token;
     use frame_support::{
-        assert_noop, assert_ok, impl_outer_origin, parameter_types,
-        traits::{Get, OnFinalize},
-        weights::Weight,
+        assert_noop, assert_ok, impl_outer_event, impl_outer_origin, parameter_types,
+        traits::{Get, OnFinalize, OnInitialize, OnRuntimeUpgrade},
+        weights::{GetDispatchInfo, Weight},
     };
     use sp_core::{H160, H256};
     use sp_runtime::{
@@ -791,11 +2886,33 @@ mod tests {
 
     thread_local! {
         static EXISTENTIAL_DEPOSIT: RefCell<u128> = RefCell::new(500);
+        static VALIDATOR_ACTIVITY_WINDOW: RefCell<u64> = RefCell::new(0);
+        static VALIDATOR_ACTIVATION_DELAY: RefCell<u64> = RefCell::new(0);
+        static PENDING_MINT_TTL: RefCell<u64> = RefCell::new(0);
+        static TRANSFER_TTL: RefCell<u64> = RefCell::new(0);
+        static DAILY_LIMIT_GRACE_BLOCKS: RefCell<u64> = RefCell::new(0);
+        static FIRST_DAY_WITHDRAW_LIMIT_PERCENT: RefCell<u32> = RefCell::new(75);
+        static VALIDATOR_CHANGE_DELAY: RefCell<u64> = RefCell::new(0);
+        static MINT_CONFIRMATION_DELAY: RefCell<u64> = RefCell::new(0);
     }
 
     impl_outer_origin! {
         pub enum Origin for Test {}
     }
+
+    mod bridge {
+        pub use crate::bridge::Event;
+    }
+
+    impl_outer_event! {
+        pub enum TestEvent for Test {
+            system<T>,
+            balances<T>,
+            token<T>,
+            bridge<T>,
+        }
+    }
+
     pub struct ExistentialDeposit;
     impl Get<u128> for ExistentialDeposit {
         fn get() -> u128 {
@@ -803,12 +2920,85 @@ mod tests {
         }
     }
 
+    pub struct ValidatorActivityWindow;
+    impl Get<u64> for ValidatorActivityWindow {
+        fn get() -> u64 {
+            VALIDATOR_ACTIVITY_WINDOW.with(|v| *v.borrow())
+        }
+    }
+
+    pub struct ValidatorActivationDelay;
+    impl Get<u64> for ValidatorActivationDelay {
+        fn get() -> u64 {
+            VALIDATOR_ACTIVATION_DELAY.with(|v| *v.borrow())
+        }
+    }
+
+    pub struct PendingMintTtl;
+    impl Get<u64> for PendingMintTtl {
+        fn get() -> u64 {
+            PENDING_MINT_TTL.with(|v| *v.borrow())
+        }
+    }
+
+    pub struct TransferTtl;
+    impl Get<u64> for TransferTtl {
+        fn get() -> u64 {
+            TRANSFER_TTL.with(|v| *v.borrow())
+        }
+    }
+
+    pub struct DailyLimitGraceBlocks;
+    impl Get<u64> for DailyLimitGraceBlocks {
+        fn get() -> u64 {
+            DAILY_LIMIT_GRACE_BLOCKS.with(|v| *v.borrow())
+        }
+    }
+
+    pub struct FirstDayWithdrawLimitPercent;
+    impl Get<u32> for FirstDayWithdrawLimitPercent {
+        fn get() -> u32 {
+            FIRST_DAY_WITHDRAW_LIMIT_PERCENT.with(|v| *v.borrow())
+        }
+    }
+
+    pub struct ValidatorChangeDelay;
+    impl Get<u64> for ValidatorChangeDelay {
+        fn get() -> u64 {
+            VALIDATOR_CHANGE_DELAY.with(|v| *v.borrow())
+        }
+    }
+
+    pub struct MintConfirmationDelay;
+    impl Get<u64> for MintConfirmationDelay {
+        fn get() -> u64 {
+            MINT_CONFIRMATION_DELAY.with(|v| *v.borrow())
+        }
+    }
+
+    // rejects every transfer of SANCTIONED
+    pub struct DenyListCompliance;
+    impl ComplianceCheck<u64, Balance> for DenyListCompliance {
+        fn verify(account: &u64, _: TokenId, _: Balance, _: Status) -> bool {
+            *account != SANCTIONED
+        }
+    }
+
+    // only DAI has a fresh oracle price
+    pub struct DaiPriceOnly;
+    impl TokenPrice for DaiPriceOnly {
+        fn has_fresh_price(token_id: TokenId) -> bool {
+            token_id == TOKEN_ID
+        }
+    }
+
     // For testing the module, we construct most of a mock runtime. This means
     // first constructing a configuration type (`Test`) which `impl`s each of the
     // configuration traits of modules we want to use.
     #[derive(Clone, Eq, PartialEq)]
     pub struct Test;
     parameter_types! {
+        pub const SlashFraction: Perbill = Perbill::from_percent(10);
         pub const BlockHashCount: u64 = 250;
         pub const MaximumBlockWeight: Weight = 1024;
         pub const MaximumBlockLength: u32 = 2 * 1024;
@@ -824,7 +3014,7 @@ mod tests {
         type AccountId = u64;
         type Lookup = IdentityLookup<Self::AccountId>;
         type Header = Header;
-        type Event = ();
+        type Event = TestEvent;
         type BlockHashCount = BlockHashCount;
         type MaximumBlockWeight = MaximumBlockWeight;
         type MaximumBlockLength = MaximumBlockLength;
@@ -839,13 +3029,17 @@ mod tests {
     impl balances::Trait for Test {
         type Balance = Balance;
         type DustRemoval = ();
-        type Event = ();
+        type Event = TestEvent;
         type ExistentialDeposit = ExistentialDeposit;
         type AccountStore = system::Module<Test>;
     }
 
     parameter_types! {
         pub const MinimumPeriod: u64 = 5;
+        pub const MinValidators: u32 = 3;
+        pub const MaxBatchSize: u32 = 3;
+        pub const MaxMintBatch: u32 = 3;
+        pub const HoldBypassBond: Balance = 1_000;
     }
     impl timestamp::Trait for Test {
         type Moment = u64;
@@ -853,16 +3047,33 @@ mod tests {
         type MinimumPeriod = MinimumPeriod;
     }
     impl token::Trait for Test {
-        type Event = ();
+        type Event = TestEvent;
     }
     impl Trait for Test {
-        type Event = ();
+        type Event = TestEvent;
+        type ValidatorActivityWindow = ValidatorActivityWindow;
+        type MinValidators = MinValidators;
+        type ValidatorActivationDelay = ValidatorActivationDelay;
+        type PendingMintTtl = PendingMintTtl;
+        type TransferTtl = TransferTtl;
+        type DailyLimitGraceBlocks = DailyLimitGraceBlocks;
+        type MaxBatchSize = MaxBatchSize;
+        type FirstDayWithdrawLimitPercent = FirstDayWithdrawLimitPercent;
+        type HoldBypassBond = HoldBypassBond;
+        type ValidatorChangeDelay = ValidatorChangeDelay;
+        type MaxMintBatch = MaxMintBatch;
+        type MintConfirmationDelay = MintConfirmationDelay;
+        type SlashFraction = SlashFraction;
+        type ComplianceCheck = DenyListCompliance;
+        type TokenPrice = DaiPriceOnly;
+        type WeightInfo = ();
     }
 
     type BridgeModule = Module<Test>;
     type TokenModule = token::Module<Test>;
     type TimestampModule = timestamp::Module<Test>;
     type System = system::Module<Test>;
+    type Balances = balances::Module<Test>;
 
     const ETH_MESSAGE_ID: &[u8; 32] = b"0x5617efe391571b5dc8230db92ba65b";
     const ETH_MESSAGE_ID1: &[u8; 32] = b"0x5617iru391571b5dc8230db92ba65b";
@@ -874,6 +3085,7 @@ mod tests {
     const ETH_MESSAGE_ID7: &[u8; 32] = b"0x5617jqu391571b5dc8230db92ba65b";
     const ETH_MESSAGE_ID8: &[u8; 32] = b"0x5617pbt391571b5dc8230db92ba65b";
     const ETH_ADDRESS: &[u8; 20] = b"0x00b46c2526ebb8f4c9";
+    const DAI_CONTRACT: &[u8; 20] = b"dai-contract-address";
     const V1: u64 = 1;
     const V2: u64 = 2;
     const V3: u64 = 3;
@@ -887,23 +3099,83 @@ mod tests {
     const USER7: u64 = 11;
     const USER8: u64 = 12;
     const USER9: u64 = 13;
+    const SANCTIONED: u64 = 14;
     const TOKEN_ID: u32 = 0;
+    const USDT_ID: u32 = 2;
+    const CHAIN_ID: ChainId = 1;
 
     pub struct ExtBuilder {
         existential_deposit: u128,
+        validator_activity_window: u64,
+        validator_activation_delay: u64,
+        pending_mint_ttl: u64,
+        transfer_ttl: u64,
+        daily_limit_grace_blocks: u64,
+        first_day_withdraw_limit_percent: u32,
+        validator_change_delay: u64,
+        mint_confirmation_delay: u64,
     }
 
     impl Default for ExtBuilder {
         fn default() -> Self {
             Self {
                 existential_deposit: 500,
+                validator_activity_window: 0,
+                validator_activation_delay: 0,
+                pending_mint_ttl: 0,
+                transfer_ttl: 0,
+                daily_limit_grace_blocks: 0,
+                first_day_withdraw_limit_percent: 75,
+                validator_change_delay: 0,
+                mint_confirmation_delay: 0,
             }
         }
     }
 
     impl ExtBuilder {
+        pub fn validator_activity_window(mut self, window: u64) -> Self {
+            self.validator_activity_window = window;
+            self
+        }
+        pub fn validator_activation_delay(mut self, delay: u64) -> Self {
+            self.validator_activation_delay = delay;
+            self
+        }
+        pub fn pending_mint_ttl(mut self, ttl: u64) -> Self {
+            self.pending_mint_ttl = ttl;
+            self
+        }
+        pub fn transfer_ttl(mut self, ttl: u64) -> Self {
+            self.transfer_ttl = ttl;
+            self
+        }
+        pub fn daily_limit_grace_blocks(mut self, blocks: u64) -> Self {
+            self.daily_limit_grace_blocks = blocks;
+            self
+        }
+        pub fn first_day_withdraw_limit_percent(mut self, percent: u32) -> Self {
+            self.first_day_withdraw_limit_percent = percent;
+            self
+        }
+        pub fn validator_change_delay(mut self, delay: u64) -> Self {
+            self.validator_change_delay = delay;
+            self
+        }
+        pub fn mint_confirmation_delay(mut self, delay: u64) -> Self {
+            self.mint_confirmation_delay = delay;
+            self
+        }
         pub fn set_associated_consts(&self) {
             EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = self.existential_deposit);
+            VALIDATOR_ACTIVITY_WINDOW.with(|v| *v.borrow_mut() = self.validator_activity_window);
+            VALIDATOR_ACTIVATION_DELAY.with(|v| *v.borrow_mut() = self.validator_activation_delay);
+            PENDING_MINT_TTL.with(|v| *v.borrow_mut() = self.pending_mint_ttl);
+            TRANSFER_TTL.with(|v| *v.borrow_mut() = self.transfer_ttl);
+            DAILY_LIMIT_GRACE_BLOCKS.with(|v| *v.borrow_mut() = self.daily_limit_grace_blocks);
+            FIRST_DAY_WITHDRAW_LIMIT_PERCENT
+                .with(|v| *v.borrow_mut() = self.first_day_withdraw_limit_percent);
+            VALIDATOR_CHANGE_DELAY.with(|v| *v.borrow_mut() = self.validator_change_delay);
+            MINT_CONFIRMATION_DELAY.with(|v| *v.borrow_mut() = self.mint_confirmation_delay);
         }
         pub fn build(self) -> sp_io::TestExternalities {
             self.set_associated_consts();
@@ -923,11 +3195,28 @@ mod tests {
             .assimilate_storage(&mut storage);
 
             let _ = token::GenesisConfig {
-                tokens: vec![Token {
-                    id: 0,
-                    decimals: 18,
-                    symbol: Vec::from("TOKEN"),
-                }],
+                tokens: vec![
+                    Token {
+                        id: 0,
+                        decimals: 18,
+                        symbol: Vec::from("DAI"),
+                    },
+                    Token {
+                        id: 1,
+                        decimals: 8,
+                        symbol: Vec::from("cDAI"),
+                    },
+                    Token {
+                        id: 2,
+                        decimals: 6,
+                        symbol: Vec::from("USDT"),
+                    },
+                    Token {
+                        id: 3,
+                        decimals: 6,
+                        symbol: Vec::from("USDC"),
+                    },
+                ],
             }
             .assimilate_storage(&mut storage);
 
@@ -935,6 +3224,9 @@ mod tests {
                 validators_count: 3u32,
                 validator_accounts: vec![V1, V2, V3],
                 current_limits: vec![100, 200, 50, 400, 1],
+                token_limits: vec![],
+                token_eth_addresses: vec![(TOKEN_ID, H160::from(DAI_CONTRACT))],
+                emergency_admins: vec![USER9],
             }
             .assimilate_storage(&mut storage);
 
@@ -949,9 +3241,20 @@ mod tests {
             BridgeModule::on_finalize(System::block_number());
             TimestampModule::set_timestamp(6 * n);
             System::set_block_number(System::block_number() + 1);
+            BridgeModule::on_initialize(System::block_number());
         }
     }
 
+    fn bridge_events() -> Vec<RawEvent<u64, H256, Balance, u64, u64>> {
+        System::events()
+            .into_iter()
+            .filter_map(|r| match r.event {
+                TestEvent::bridge(e) => Some(e),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// KNOWN BUGS:
     ///     1. Tests can fail with assert_noop! bug: fails through different root hashes
     ///        looks like gibberish bytes:
@@ -965,7 +3268,7 @@ mod tests {
         ExtBuilder::default().build().execute_with(|| {
             let message_id = H256::from(ETH_MESSAGE_ID);
             let eth_address = H160::from(ETH_ADDRESS);
-            let amount = 99;
+            let amount = 49;
 
             let token = TokenModule::token_map(0);
             println!("{:?}", token);
@@ -973,6 +3276,7 @@ mod tests {
             //substrate <----- ETH
             assert_ok!(BridgeModule::multi_signed_mint(
                 Origin::signed(V2),
+                CHAIN_ID,
                 message_id,
                 eth_address,
                 USER2,
@@ -984,6 +3288,7 @@ mod tests {
 
             assert_ok!(BridgeModule::multi_signed_mint(
                 Origin::signed(V1),
+                CHAIN_ID,
                 message_id,
                 eth_address,
                 USER2,
@@ -1005,11 +3310,12 @@ mod tests {
         ExtBuilder::default().build().execute_with(|| {
             let message_id = H256::from(ETH_MESSAGE_ID);
             let eth_address = H160::from(ETH_ADDRESS);
-            let amount = 99;
+            let amount = 49;
 
             //substrate <----- ETH
             assert_ok!(BridgeModule::multi_signed_mint(
                 Origin::signed(V2),
+                CHAIN_ID,
                 message_id,
                 eth_address,
                 USER2,
@@ -1018,6 +3324,7 @@ mod tests {
             ));
             assert_ok!(BridgeModule::multi_signed_mint(
                 Origin::signed(V1),
+                CHAIN_ID,
                 message_id,
                 eth_address,
                 USER2,
@@ -1027,13 +3334,14 @@ mod tests {
             assert_noop!(
                 BridgeModule::multi_signed_mint(
                     Origin::signed(V3),
+                    CHAIN_ID,
                     message_id,
                     eth_address,
                     USER2,
                     TOKEN_ID,
                     amount
                 ),
-                "This transfer is not open"
+                "Message already processed"
             );
             assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), amount);
             assert_eq!(TokenModule::total_supply(TOKEN_ID), amount);
@@ -1057,9 +3365,11 @@ mod tests {
             //substrate ----> ETH
             assert_ok!(BridgeModule::set_transfer(
                 Origin::signed(USER2),
+                CHAIN_ID,
                 eth_address,
                 TOKEN_ID,
-                amount2
+                amount2,
+                false
             ));
             //RelayMessage(message_id) event emitted
 
@@ -1124,9 +3434,11 @@ mod tests {
             //substrate ----> ETH
             assert_ok!(BridgeModule::set_transfer(
                 Origin::signed(USER2),
+                CHAIN_ID,
                 eth_address,
                 TOKEN_ID,
-                amount2
+                amount2,
+                false
             ));
             //RelayMessage(message_id) event emitted
 
@@ -1155,9 +3467,11 @@ mod tests {
             //substrate ----> ETH
             assert_ok!(BridgeModule::set_transfer(
                 Origin::signed(USER2),
+                CHAIN_ID,
                 eth_address,
                 TOKEN_ID,
-                amount2
+                amount2,
+                false
             ));
 
             let sub_message_id = BridgeModule::message_id_by_transfer_id(0);
@@ -1196,9 +3510,11 @@ mod tests {
             //substrate ----> ETH
             assert_ok!(BridgeModule::set_transfer(
                 Origin::signed(USER2),
+                CHAIN_ID,
                 eth_address,
                 TOKEN_ID,
-                amount2
+                amount2,
+                false
             ));
 
             let sub_message_id = BridgeModule::message_id_by_transfer_id(0);
@@ -1308,6 +3624,7 @@ mod tests {
             assert_noop!(
                 BridgeModule::multi_signed_mint(
                     Origin::signed(V2),
+                    CHAIN_ID,
                     eth_message_id,
                     eth_address,
                     USER2,
@@ -1359,12 +3676,13 @@ mod tests {
         ExtBuilder::default().build().execute_with(|| {
             let eth_message_id = H256::from(ETH_MESSAGE_ID);
             let eth_address = H160::from(ETH_ADDRESS);
-            let amount1 = 99;
-            let amount2 = 49;
+            let amount1 = 49;
+            let amount2 = 48;
 
             //substrate <----- ETH
             assert_ok!(BridgeModule::multi_signed_mint(
                 Origin::signed(V2),
+                CHAIN_ID,
                 eth_message_id,
                 eth_address,
                 USER2,
@@ -1373,6 +3691,7 @@ mod tests {
             ));
             assert_ok!(BridgeModule::multi_signed_mint(
                 Origin::signed(V1),
+                CHAIN_ID,
                 eth_message_id,
                 eth_address,
                 USER2,
@@ -1382,9 +3701,11 @@ mod tests {
             //substrate ----> ETH
             assert_ok!(BridgeModule::set_transfer(
                 Origin::signed(USER2),
+                CHAIN_ID,
                 eth_address,
                 TOKEN_ID,
-                amount2
+                amount2,
+                false
             ));
             //RelayMessage(message_id) event emitted
             let sub_message_id = BridgeModule::message_id_by_transfer_id(1);
@@ -1403,7 +3724,7 @@ mod tests {
             assert_eq!(
                 BridgeModule::approve_transfer(Origin::signed(V2), sub_message_id),
                 Err(DispatchError::Other(
-                    "Cannot withdraw more that 75% of first day deposit."
+                    "Cannot withdraw more than the allowed share of first day deposit."
                 ))
             );
 
@@ -1423,6 +3744,7 @@ mod tests {
             assert_eq!(BridgeModule::current_limits().max_tx_value, 100);
             assert_ok!(BridgeModule::update_limits(
                 Origin::signed(V2),
+                None,
                 max_tx_value,
                 day_max_limit,
                 day_max_limit_for_one_address,
@@ -1431,6 +3753,7 @@ mod tests {
             ));
             assert_ok!(BridgeModule::update_limits(
                 Origin::signed(V1),
+                None,
                 max_tx_value,
                 day_max_limit,
                 day_max_limit_for_one_address,
@@ -1453,6 +3776,7 @@ mod tests {
             assert_noop!(
                 BridgeModule::update_limits(
                     Origin::signed(V1),
+                    None,
                     MORE_THAN_MAX,
                     day_max_limit,
                     day_max_limit_for_one_address,
@@ -1482,9 +3806,11 @@ mod tests {
             //1
             assert_ok!(BridgeModule::set_transfer(
                 Origin::signed(USER2),
+                CHAIN_ID,
                 eth_address,
                 TOKEN_ID,
-                amount2
+                amount2,
+                false
             ));
             let sub_message_id = BridgeModule::message_id_by_transfer_id(0);
             assert_ok!(BridgeModule::approve_transfer(
@@ -1493,9 +3819,11 @@ mod tests {
             ));
             assert_ok!(BridgeModule::set_transfer(
                 Origin::signed(USER3),
+                CHAIN_ID,
                 eth_address,
                 TOKEN_ID,
-                amount2
+                amount2,
+                false
             ));
             let sub_message_id = BridgeModule::message_id_by_transfer_id(1);
             assert_ok!(BridgeModule::approve_transfer(
@@ -1504,9 +3832,11 @@ mod tests {
             ));
             assert_ok!(BridgeModule::set_transfer(
                 Origin::signed(USER4),
+                CHAIN_ID,
                 eth_address,
                 TOKEN_ID,
-                amount2
+                amount2,
+                false
             ));
             let sub_message_id = BridgeModule::message_id_by_transfer_id(2);
             assert_ok!(BridgeModule::approve_transfer(
@@ -1515,9 +3845,11 @@ mod tests {
             ));
             assert_ok!(BridgeModule::set_transfer(
                 Origin::signed(USER5),
+                CHAIN_ID,
                 eth_address,
                 TOKEN_ID,
-                amount2
+                amount2,
+                false
             ));
             let sub_message_id = BridgeModule::message_id_by_transfer_id(3);
             assert_ok!(BridgeModule::approve_transfer(
@@ -1526,9 +3858,11 @@ mod tests {
             ));
             assert_ok!(BridgeModule::set_transfer(
                 Origin::signed(USER6),
+                CHAIN_ID,
                 eth_address,
                 TOKEN_ID,
-                amount2
+                amount2,
+                false
             ));
             let sub_message_id = BridgeModule::message_id_by_transfer_id(4);
             assert_ok!(BridgeModule::approve_transfer(
@@ -1537,9 +3871,11 @@ mod tests {
             ));
             assert_ok!(BridgeModule::set_transfer(
                 Origin::signed(USER7),
+                CHAIN_ID,
                 eth_address,
                 TOKEN_ID,
-                amount2
+                amount2,
+                false
             ));
             let sub_message_id = BridgeModule::message_id_by_transfer_id(5);
             assert_ok!(BridgeModule::approve_transfer(
@@ -1548,9 +3884,11 @@ mod tests {
             ));
             assert_ok!(BridgeModule::set_transfer(
                 Origin::signed(USER8),
+                CHAIN_ID,
                 eth_address,
                 TOKEN_ID,
-                amount2
+                amount2,
+                false
             ));
             let sub_message_id = BridgeModule::message_id_by_transfer_id(6);
             assert_ok!(BridgeModule::approve_transfer(
@@ -1559,9 +3897,11 @@ mod tests {
             ));
             assert_ok!(BridgeModule::set_transfer(
                 Origin::signed(USER9),
+                CHAIN_ID,
                 eth_address,
                 TOKEN_ID,
-                amount2
+                amount2,
+                false
             ));
             let sub_message_id = BridgeModule::message_id_by_transfer_id(7);
             assert_ok!(BridgeModule::approve_transfer(
@@ -1569,195 +3909,4061 @@ mod tests {
                 sub_message_id
             ));
 
-            assert_eq!(BridgeModule::pending_burn_count(), amount2 * 8);
+            assert_eq!(BridgeModule::pending_burn_count(), amount2 * 8);
+            assert_eq!(
+                BridgeModule::set_transfer(Origin::signed(USER1), CHAIN_ID, eth_address, TOKEN_ID, amount2, false),
+                Err(DispatchError::Other("Too many pending burn transactions."))
+            );
+        })
+    }
+    #[test]
+    fn pending_mint_limit_should_work() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_message_id = H256::from(ETH_MESSAGE_ID);
+            let eth_message_id1 = H256::from(ETH_MESSAGE_ID1);
+            let eth_message_id2 = H256::from(ETH_MESSAGE_ID2);
+            let eth_message_id3 = H256::from(ETH_MESSAGE_ID3);
+            let eth_message_id4 = H256::from(ETH_MESSAGE_ID4);
+            let eth_message_id5 = H256::from(ETH_MESSAGE_ID5);
+            let eth_message_id6 = H256::from(ETH_MESSAGE_ID6);
+            let eth_message_id7 = H256::from(ETH_MESSAGE_ID7);
+            let eth_message_id8 = H256::from(ETH_MESSAGE_ID8);
+            let eth_address = H160::from(ETH_ADDRESS);
+            let amount1 = 49;
+
+            //substrate <----- ETH
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V2),
+                CHAIN_ID,
+                eth_message_id,
+                eth_address,
+                USER2,
+                TOKEN_ID,
+                amount1
+            ));
+
+            //substrate <----- ETH
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V2),
+                CHAIN_ID,
+                eth_message_id2,
+                eth_address,
+                USER3,
+                TOKEN_ID,
+                amount1
+            ));
+
+            //substrate <----- ETH
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V2),
+                CHAIN_ID,
+                eth_message_id3,
+                eth_address,
+                USER4,
+                TOKEN_ID,
+                amount1
+            ));
+
+            //substrate <----- ETH
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V2),
+                CHAIN_ID,
+                eth_message_id4,
+                eth_address,
+                USER5,
+                TOKEN_ID,
+                amount1
+            ));
+            //substrate <----- ETH
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V2),
+                CHAIN_ID,
+                eth_message_id5,
+                eth_address,
+                USER6,
+                TOKEN_ID,
+                amount1
+            ));
+            //substrate <----- ETH
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V2),
+                CHAIN_ID,
+                eth_message_id6,
+                eth_address,
+                USER7,
+                TOKEN_ID,
+                amount1
+            ));
+            //substrate <----- ETH
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V2),
+                CHAIN_ID,
+                eth_message_id7,
+                eth_address,
+                USER8,
+                TOKEN_ID,
+                amount1
+            ));
+            //substrate <----- ETH
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V2),
+                CHAIN_ID,
+                eth_message_id8,
+                eth_address,
+                USER9,
+                TOKEN_ID,
+                amount1
+            ));
+            assert_eq!(BridgeModule::pending_mint_count(), amount1 * 8);
+
+            //substrate <----- ETH
+            assert_noop!(
+                BridgeModule::multi_signed_mint(
+                    Origin::signed(V2),
+                    CHAIN_ID,
+                    eth_message_id1,
+                    eth_address,
+                    USER1,
+                    TOKEN_ID,
+                    amount1 + 5
+                ),
+                "Too many pending mint transactions."
+            );
+        })
+    }
+    #[test]
+    fn blocking_account_by_volume_should_work() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let amount1 = 600;
+            let amount2 = 49;
+            let _ = TokenModule::_mint(TOKEN_ID, USER2, amount1);
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                amount2,
+                false
+            ));
+            let sub_message_id = BridgeModule::message_id_by_transfer_id(0);
+            assert_ok!(BridgeModule::approve_transfer(
+                Origin::signed(V1),
+                sub_message_id
+            ));
+            assert_ok!(BridgeModule::approve_transfer(
+                Origin::signed(V2),
+                sub_message_id
+            ));
+
+            assert_eq!(
+                BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, amount2, false),
+                Err(DispatchError::Other(
+                    "Transfer declined, user blocked due to daily volume limit."
+                ))
+            );
+        })
+    }
+    #[test]
+    fn blocked_account_unblocked_next_day_should_work() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let amount1 = 600;
+            let amount2 = 49;
+            run_to_block(DAY_IN_BLOCKS.into());
+
+            let _ = TokenModule::_mint(TOKEN_ID, USER2, amount1);
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                amount2,
+                false
+            ));
+            let sub_message_id = BridgeModule::message_id_by_transfer_id(0);
+            assert_ok!(BridgeModule::approve_transfer(
+                Origin::signed(V1),
+                sub_message_id
+            ));
+            assert_ok!(BridgeModule::approve_transfer(
+                Origin::signed(V2),
+                sub_message_id
+            ));
+            assert_eq!(
+                BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, amount2, false),
+                Err(DispatchError::Other(
+                    "Transfer declined, user blocked due to daily volume limit."
+                ))
+            );
+
+            //user added to blocked vec
+            let blocked_vec: Vec<u64> = vec![USER2];
+            assert_eq!(BridgeModule::daily_blocked((TOKEN_ID, 1)), blocked_vec);
+
+            run_to_block((DAY_IN_BLOCKS * 2).into());
+            run_to_block((DAY_IN_BLOCKS * 3).into());
+
+            //try again
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                amount2,
+                false
+            ));
+        })
+    }
+    #[test]
+    fn block_digest_should_summarize_activity() {
+        ExtBuilder::default().build().execute_with(|| {
+            System::set_block_number(1);
+            let eth_message_id = H256::from(ETH_MESSAGE_ID);
+            let eth_address = H160::from(ETH_ADDRESS);
+            let amount1 = 600;
+            let amount2 = 49;
+            let _ = TokenModule::_mint(TOKEN_ID, USER1, amount1);
+            let _ = TokenModule::_mint(TOKEN_ID, USER2, amount1);
+
+            // approved withdraw
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                amount2,
+                false
+            ));
+            let sub_message_id = BridgeModule::message_id_by_transfer_id(0);
+            assert_ok!(BridgeModule::approve_transfer(
+                Origin::signed(V1),
+                sub_message_id
+            ));
+            assert_ok!(BridgeModule::approve_transfer(
+                Origin::signed(V2),
+                sub_message_id
+            ));
+
+            // executed mint
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                CHAIN_ID,
+                eth_message_id,
+                eth_address,
+                USER3,
+                TOKEN_ID,
+                amount2
+            ));
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V2),
+                CHAIN_ID,
+                eth_message_id,
+                eth_address,
+                USER3,
+                TOKEN_ID,
+                amount2
+            ));
+
+            // approved and then canceled withdraw
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER1),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                amount2,
+                false
+            ));
+            let sub_message_id = BridgeModule::message_id_by_transfer_id(2);
+            assert_ok!(BridgeModule::approve_transfer(
+                Origin::signed(V1),
+                sub_message_id
+            ));
+            assert_ok!(BridgeModule::approve_transfer(
+                Origin::signed(V2),
+                sub_message_id
+            ));
+            assert_ok!(BridgeModule::cancel_transfer(
+                Origin::signed(V2),
+                sub_message_id
+            ));
+            assert_ok!(BridgeModule::cancel_transfer(
+                Origin::signed(V3),
+                sub_message_id
+            ));
+
+            BridgeModule::on_finalize(1);
+            let digest = BridgeDigest {
+                transfers_opened: 3,
+                approvals: 2,
+                confirmations: 1,
+                cancellations: 1,
+            };
+            assert_eq!(
+                bridge_events().last(),
+                Some(&RawEvent::BlockBridgeDigest(digest))
+            );
+            assert_eq!(BridgeModule::block_digest(), BridgeDigest::default());
+        })
+    }
+    #[test]
+    fn permissioned_token_mint_to_allowlisted_recipient_should_work() {
+        ExtBuilder::default().build().execute_with(|| {
+            let message_id = H256::from(ETH_MESSAGE_ID);
+            let eth_address = H160::from(ETH_ADDRESS);
+            let amount = 49;
+
+            assert_ok!(BridgeModule::set_token_permissioned(
+                Origin::ROOT,
+                TOKEN_ID,
+                true
+            ));
+            assert_ok!(BridgeModule::set_mint_recipient(
+                Origin::ROOT,
+                TOKEN_ID,
+                USER2,
+                true
+            ));
+
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                CHAIN_ID,
+                message_id,
+                eth_address,
+                USER2,
+                TOKEN_ID,
+                amount
+            ));
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V2),
+                CHAIN_ID,
+                message_id,
+                eth_address,
+                USER2,
+                TOKEN_ID,
+                amount
+            ));
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), amount);
+        })
+    }
+    #[test]
+    fn permissioned_token_mint_to_other_recipient_should_fail() {
+        ExtBuilder::default().build().execute_with(|| {
+            let message_id = H256::from(ETH_MESSAGE_ID);
+            let eth_address = H160::from(ETH_ADDRESS);
+            let amount = 99;
+
+            assert_ok!(BridgeModule::set_token_permissioned(
+                Origin::ROOT,
+                TOKEN_ID,
+                true
+            ));
+            assert_ok!(BridgeModule::set_mint_recipient(
+                Origin::ROOT,
+                TOKEN_ID,
+                USER2,
+                true
+            ));
+
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                CHAIN_ID,
+                message_id,
+                eth_address,
+                USER1,
+                TOKEN_ID,
+                amount
+            ));
+            assert_eq!(
+                BridgeModule::multi_signed_mint(
+                    Origin::signed(V2),
+                    CHAIN_ID,
+                    message_id,
+                    eth_address,
+                    USER1,
+                    TOKEN_ID,
+                    amount
+                ),
+                Err(DispatchError::Other(
+                    "Mint recipient is not allowlisted for this token"
+                ))
+            );
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER1)), 0);
+        })
+    }
+    #[test]
+    fn open_proposals_progress_should_work() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(BridgeModule::pause_bridge(Origin::signed(V1)));
+            assert_ok!(BridgeModule::update_limits(
+                Origin::signed(V2),
+                None,
+                10,
+                20,
+                5,
+                40,
+                1
+            ));
+
+            assert_eq!(
+                BridgeModule::open_proposals_progress(),
+                vec![(0, Kind::Bridge, 1, 2), (1, Kind::Limits, 1, 2)]
+            );
+
+            assert_ok!(BridgeModule::pause_bridge(Origin::signed(V2)));
+            assert_eq!(
+                BridgeModule::open_proposals_progress(),
+                vec![(1, Kind::Limits, 1, 2)]
+            );
+
+            // a burn reopened for confirmation keeps its place by id
+            assert_ok!(BridgeModule::resume_bridge(Origin::signed(V1)));
+            assert_ok!(BridgeModule::resume_bridge(Origin::signed(V2)));
+            let _ = TokenModule::_mint(TOKEN_ID, USER2, 100);
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                H160::from(ETH_ADDRESS),
+                TOKEN_ID,
+                10,
+                false
+            ));
+            let message_id = BridgeModule::message_id_by_transfer_id(3);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), message_id));
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V2), message_id));
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V3), None, 10, 20, 5, 40, 2));
+            assert_ok!(BridgeModule::confirm_transfer(Origin::signed(V1), message_id));
+            assert_eq!(
+                BridgeModule::open_proposals_progress(),
+                vec![(1, Kind::Limits, 1, 2), (3, Kind::Transfer, 1, 2), (4, Kind::Limits, 1, 2)]
+            );
+        })
+    }
+
+    #[test]
+    fn daily_hold_should_be_per_token() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_message_id = H256::from(ETH_MESSAGE_ID);
+            let eth_address = H160::from(ETH_ADDRESS);
+            // the first day of the chain is over
+            System::set_block_number(DAY_IN_BLOCKS.into());
+
+            // minted DAI puts USER2 on hold for DAI only
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                CHAIN_ID,
+                eth_message_id,
+                eth_address,
+                USER2,
+                TOKEN_ID,
+                49
+            ));
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V2),
+                CHAIN_ID,
+                eth_message_id,
+                eth_address,
+                USER2,
+                TOKEN_ID,
+                49
+            ));
+            assert!(BridgeModule::daily_holds((TOKEN_ID, USER2)) != Default::default());
+            assert_ok!(TokenModule::_mint(USDT_ID, USER2, 60));
+
+            // more than 75% of USDT goes through, it was never deposited
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                eth_address,
+                USDT_ID,
+                49,
+                false
+            ));
+            let usdt_id = BridgeModule::message_id_by_transfer_id(1);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), usdt_id));
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V2), usdt_id));
+            assert_eq!(BridgeModule::messages(usdt_id).status, Status::Approved);
+
+            // while DAI is still restricted
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                48,
+                false
+            ));
+            let dai_id = BridgeModule::message_id_by_transfer_id(2);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), dai_id));
+            assert_eq!(
+                BridgeModule::approve_transfer(Origin::signed(V2), dai_id),
+                Err(DispatchError::Other(
+                    "Cannot withdraw more than the allowed share of first day deposit."
+                ))
+            );
+        })
+    }
+
+    #[test]
+    fn first_day_hold_should_cap_accounts_without_deposit() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            for user in [USER2, USER3].iter() {
+                assert_ok!(TokenModule::_mint(TOKEN_ID, *user, 60));
+            }
+
+            assert_ok!(BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, 49, false));
+            let capped = BridgeModule::message_id_by_transfer_id(0);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), capped));
+            assert_eq!(
+                BridgeModule::approve_transfer(Origin::signed(V2), capped),
+                Err(DispatchError::Other(
+                    "Cannot withdraw more than the allowed share of first day deposit."
+                ))
+            );
+
+            System::set_block_number(DAY_IN_BLOCKS.into());
+            assert_ok!(BridgeModule::set_transfer(Origin::signed(USER3), CHAIN_ID, eth_address, TOKEN_ID, 49, false));
+            let released = BridgeModule::message_id_by_transfer_id(1);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), released));
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V2), released));
+            assert_eq!(BridgeModule::messages(released).status, Status::Approved);
+        })
+    }
+
+    #[test]
+    fn legacy_daily_hold_should_migrate_to_every_token() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert!(BridgeModule::daily_holds_migrated());
+            <DailyHoldsMigrated>::put(false);
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                CHAIN_ID,
+                H256::from(ETH_MESSAGE_ID),
+                H160::from(ETH_ADDRESS),
+                USER1,
+                TOKEN_ID,
+                10
+            ));
+            let hold = (0u64, H256::from(ETH_MESSAGE_ID));
+            let hash = USER1.using_encoded(sp_io::hashing::blake2_256);
+            frame_support::storage::migration::put_storage_value(
+                b"Bridge",
+                b"DailyHolds",
+                &hash,
+                hold,
+            );
+
+            BridgeModule::on_runtime_upgrade();
+
+            for id in 0..4 {
+                assert_eq!(BridgeModule::daily_holds((id, USER1)), hold);
+            }
+            let legacy: Option<(u64, H256)> =
+                take_storage_value(b"Bridge", b"DailyHolds", &hash);
+            assert_eq!(legacy, None);
+            assert!(BridgeModule::daily_holds_migrated());
+        })
+    }
+
+    #[test]
+    fn reorg_reported_by_quorum_should_cancel_pending_mint() {
+        ExtBuilder::default().build().execute_with(|| {
+            let message_id = H256::from(ETH_MESSAGE_ID);
+            let eth_address = H160::from(ETH_ADDRESS);
+            let amount = 99;
+
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                CHAIN_ID,
+                message_id,
+                eth_address,
+                USER2,
+                TOKEN_ID,
+                amount
+            ));
+            assert_eq!(BridgeModule::pending_mint_count(), amount);
+
+            System::set_block_number(1);
+            assert_ok!(BridgeModule::report_eth_reorg(Origin::signed(V1), message_id));
+            assert_eq!(BridgeModule::messages(message_id).status, Status::Pending);
+            assert_ok!(BridgeModule::report_eth_reorg(Origin::signed(V2), message_id));
+
+            assert_eq!(BridgeModule::messages(message_id).status, Status::Canceled);
+            assert_eq!(BridgeModule::pending_mint_count(), 0);
+            assert!(!BridgeModule::transfers(0).open);
+            assert!(bridge_events().contains(&RawEvent::MintReorgCancelled(message_id, TOKEN_ID)));
+
+            assert_noop!(
+                BridgeModule::multi_signed_mint(
+                    Origin::signed(V2),
+                    CHAIN_ID,
+                    message_id,
+                    eth_address,
+                    USER2,
+                    TOKEN_ID,
+                    amount
+                ),
+                DispatchError::Other("This transfer is not open")
+            );
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 0);
+        })
+    }
+
+    #[test]
+    fn reorg_reports_of_removed_validators_should_not_count() {
+        ExtBuilder::default().build().execute_with(|| {
+            let message_id = H256::from(ETH_MESSAGE_ID);
+            let eth_address = H160::from(ETH_ADDRESS);
+
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                CHAIN_ID,
+                message_id,
+                eth_address,
+                USER2,
+                TOKEN_ID,
+                10
+            ));
+            assert_ok!(BridgeModule::report_eth_reorg(Origin::signed(V3), message_id));
+
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::update_validator_list(
+                    Origin::signed(*v),
+                    H256::from(ETH_MESSAGE_ID1),
+                    2,
+                    vec![V1, V2, V4]
+                ));
+            }
+            assert!(!BridgeModule::validators(V3));
+
+            assert_ok!(BridgeModule::report_eth_reorg(Origin::signed(V1), message_id));
+            assert_eq!(BridgeModule::reorg_votes_count(message_id), 2);
+            assert_eq!(BridgeModule::messages(message_id).status, Status::Pending);
+
+            assert_ok!(BridgeModule::report_eth_reorg(Origin::signed(V2), message_id));
+            assert_eq!(BridgeModule::messages(message_id).status, Status::Canceled);
+        })
+    }
+
+    #[test]
+    fn reorg_report_of_inactive_validator_should_fail() {
+        ExtBuilder::default()
+            .validator_activation_delay(5)
+            .build()
+            .execute_with(|| {
+                let message_id = H256::from(ETH_MESSAGE_ID);
+                System::set_block_number(1);
+
+                for v in [V1, V2].iter() {
+                    assert_ok!(BridgeModule::update_validator_list(
+                        Origin::signed(*v),
+                        H256::from(ETH_MESSAGE_ID1),
+                        3,
+                        vec![V1, V2, V3, V4]
+                    ));
+                }
+                assert_ok!(BridgeModule::multi_signed_mint(
+                    Origin::signed(V1),
+                    CHAIN_ID,
+                    message_id,
+                    H160::from(ETH_ADDRESS),
+                    USER2,
+                    TOKEN_ID,
+                    10
+                ));
+
+                System::set_block_number(5);
+                assert_noop!(
+                    BridgeModule::report_eth_reorg(Origin::signed(V4), message_id),
+                    DispatchError::Other("Validator is not active yet")
+                );
+                System::set_block_number(6);
+                assert_ok!(BridgeModule::report_eth_reorg(Origin::signed(V4), message_id));
+            })
+    }
+
+    #[test]
+    fn reorg_of_executed_mint_should_fail() {
+        ExtBuilder::default().build().execute_with(|| {
+            let message_id = H256::from(ETH_MESSAGE_ID);
+            let eth_address = H160::from(ETH_ADDRESS);
+
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                CHAIN_ID,
+                message_id,
+                eth_address,
+                USER2,
+                TOKEN_ID,
+                49
+            ));
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V2),
+                CHAIN_ID,
+                message_id,
+                eth_address,
+                USER2,
+                TOKEN_ID,
+                49
+            ));
+
+            assert_noop!(
+                BridgeModule::report_eth_reorg(Origin::signed(V1), message_id),
+                DispatchError::Other("Mint is already executed, reorg can't be undone")
+            );
+        })
+    }
+
+    #[test]
+    fn votes_are_enough_should_compare_with_quorum() {
+        ExtBuilder::default().build().execute_with(|| {
+            // (quorum, votes, enough)
+            let matrix: [(u64, MemberId, bool); 7] = [
+                (1, 0, false),
+                (1, 1, true),
+                (2, 1, false),
+                (2, 2, true),
+                (2, 3, true),
+                (3, 2, false),
+                (3, 3, true),
+            ];
+            for (quorum, votes, enough) in matrix.iter() {
+                Quorum::put(quorum);
+                assert_eq!(BridgeModule::votes_are_enough(*votes), *enough);
+            }
+        })
+    }
+
+    #[test]
+    fn update_validator_list_with_zero_quorum_should_fail() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_message_id = H256::from(ETH_MESSAGE_ID);
+
+            assert_noop!(
+                BridgeModule::update_validator_list(
+                    Origin::signed(V1),
+                    eth_message_id,
+                    0,
+                    vec![V1, V2, V3, V4]
+                ),
+                DispatchError::Other("Quorum can't be zero")
+            );
+            assert_eq!(BridgeModule::quorum(), 2);
+        })
+    }
+
+    #[test]
+    fn emergency_unlock_should_return_locked_funds() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            assert_ok!(TokenModule::_mint(TOKEN_ID, USER2, 100));
+
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                40,
+                false
+            ));
+            let message_id = BridgeModule::message_id_by_transfer_id(0);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), message_id));
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V2), message_id));
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 40);
+
+            assert_noop!(
+                BridgeModule::emergency_unlock(Origin::signed(USER2), message_id),
+                DispatchError::Other("Emergency exit is not enabled")
+            );
+            assert_ok!(BridgeModule::enable_emergency_exit(Origin::ROOT));
+            assert!(!BridgeModule::bridge_is_operational());
+
+            assert_noop!(
+                BridgeModule::emergency_unlock(Origin::signed(USER1), message_id),
+                DispatchError::Other("Only the owner can unlock the transfer")
+            );
+            assert_ok!(BridgeModule::emergency_unlock(Origin::signed(USER2), message_id));
+
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 0);
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 100);
+            assert_eq!(BridgeModule::daily_limits_by_account((TOKEN_ID, USER2)), 0);
+            assert_eq!(BridgeModule::messages(message_id).status, Status::Canceled);
+            assert_noop!(
+                BridgeModule::emergency_unlock(Origin::signed(USER2), message_id),
+                DispatchError::Other("This transfer has nothing to unlock")
+            );
+        })
+    }
+
+    #[test]
+    fn limit_history_should_record_confirmed_changes() {
+        ExtBuilder::default().build().execute_with(|| {
+            let first = Limits {
+                max_tx_value: 10,
+                day_max_limit: 20,
+                day_max_limit_for_one_address: 5,
+                max_pending_tx_limit: 40,
+                min_tx_value: 1,
+            };
+            let second = Limits {
+                max_tx_value: 30,
+                ..first.clone()
+            };
+
+            System::set_block_number(2);
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::update_limits(Origin::signed(*v), None, 10, 20, 5, 40, 1));
+            }
+            System::set_block_number(5);
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::update_limits(Origin::signed(*v), None, 30, 20, 5, 40, 1));
+            }
+
+            assert_eq!(
+                BridgeModule::limit_history(),
+                vec![(2, first.clone()), (5, second.clone())]
+            );
+            assert_eq!(
+                BridgeModule::limit_history_newest_first(),
+                vec![(5, second), (2, first)]
+            );
+        })
+    }
+
+    #[test]
+    fn transfers_should_wait_for_active_validators() {
+        ExtBuilder::default()
+            .validator_activity_window(10)
+            .build()
+            .execute_with(|| {
+                let eth_address = H160::from(ETH_ADDRESS);
+                let not_ready =
+                    DispatchError::Other("Bridge not ready — insufficient active validators.");
+                System::set_block_number(1);
+
+                assert_noop!(
+                    BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, 10, false),
+                    not_ready
+                );
+                assert_ok!(BridgeModule::multi_signed_mint(
+                    Origin::signed(V1),
+                    CHAIN_ID,
+                    H256::from(ETH_MESSAGE_ID1),
+                    eth_address,
+                    USER1,
+                    TOKEN_ID,
+                    10
+                ));
+                assert_noop!(
+                    BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, 10, false),
+                    not_ready
+                );
+                assert_ok!(BridgeModule::multi_signed_mint(
+                    Origin::signed(V2),
+                    CHAIN_ID,
+                    H256::from(ETH_MESSAGE_ID2),
+                    eth_address,
+                    USER1,
+                    TOKEN_ID,
+                    10
+                ));
+                assert_ok!(BridgeModule::set_transfer(
+                    Origin::signed(USER2),
+                    CHAIN_ID,
+                    eth_address,
+                    TOKEN_ID,
+                    10,
+                    false
+                ));
+
+                // validators went silent
+                System::set_block_number(12);
+                assert_noop!(
+                    BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, 11, false),
+                    not_ready
+                );
+            })
+    }
+
+    #[test]
+    fn withdraw_limit_proposal_should_work() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V2), None, 10, 20, 5, 40, 1));
+            let message_id = BridgeModule::message_id_by_transfer_id(0);
+            assert_eq!(BridgeModule::open_proposals_progress().len(), 1);
+
+            assert_ok!(BridgeModule::withdraw_limit_proposal(Origin::signed(V2), message_id));
+
+            assert_eq!(BridgeModule::limit_messages(message_id).status, Status::Canceled);
+            assert!(!<TransferId<Test>>::contains_key(message_id));
+            assert!(!BridgeModule::transfers(0).open);
+            assert!(bridge_events().contains(&RawEvent::LimitProposalWithdrawn(message_id)));
+            assert_noop!(
+                BridgeModule::withdraw_limit_proposal(Origin::signed(V2), message_id),
+                DispatchError::Other("This limit proposal is already withdrawn")
+            );
+            assert!(BridgeModule::open_proposals_progress().is_empty());
+            assert_eq!(BridgeModule::current_limits().max_tx_value, 100);
+
+            // the same limits go through as a fresh proposal
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V1), None, 10, 20, 5, 40, 1));
+            assert_eq!(BridgeModule::transfer_id_by_hash(message_id), 1);
+            assert_eq!(BridgeModule::transfers(1).votes, 1);
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V2), None, 10, 20, 5, 40, 1));
+            assert_eq!(BridgeModule::current_limits().max_tx_value, 10);
+        })
+    }
+
+    #[test]
+    fn withdraw_limit_proposal_by_quorum_should_work() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V1), None, 10, 20, 5, 40, 1));
+            let message_id = BridgeModule::message_id_by_transfer_id(0);
+
+            assert_ok!(BridgeModule::withdraw_limit_proposal(Origin::signed(V2), message_id));
+            assert!(BridgeModule::transfers(0).open);
+            assert_eq!(BridgeModule::limit_messages(message_id).status, Status::Pending);
+            assert_ok!(BridgeModule::withdraw_limit_proposal(Origin::signed(V3), message_id));
+
+            assert!(!BridgeModule::transfers(0).open);
+            assert!(!<TransferId<Test>>::contains_key(message_id));
+            assert_eq!(BridgeModule::limit_messages(message_id).status, Status::Canceled);
+            assert!(BridgeModule::open_proposals_progress().is_empty());
+            assert_eq!(BridgeModule::current_limits().max_tx_value, 100);
+
+            // votes to withdraw a previous proposal of the same limits don't carry over
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V1), None, 10, 20, 5, 40, 1));
+            assert_ok!(BridgeModule::withdraw_limit_proposal(Origin::signed(V2), message_id));
+            assert_ok!(BridgeModule::withdraw_limit_proposal(Origin::signed(V1), message_id));
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V1), None, 10, 20, 5, 40, 1));
+            let transfer_id = BridgeModule::transfer_id_by_hash(message_id);
+            assert_ok!(BridgeModule::withdraw_limit_proposal(Origin::signed(V2), message_id));
+            assert!(BridgeModule::transfers(transfer_id).open);
+        })
+    }
+
+    #[test]
+    fn proposals_awaiting_should_work() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V1), None, 10, 20, 5, 40, 1));
+            assert_ok!(BridgeModule::pause_bridge(Origin::signed(V3)));
+
+            assert_eq!(
+                BridgeModule::proposals_awaiting(V1),
+                vec![(1, Kind::Bridge)]
+            );
+            assert_eq!(
+                BridgeModule::proposals_awaiting(V2),
+                vec![(0, Kind::Limits), (1, Kind::Bridge)]
+            );
+        })
+    }
+
+    #[test]
+    fn transfer_to_contract_destination_should_require_override() {
+        ExtBuilder::default().build().execute_with(|| {
+            let contract = H160::from(ETH_ADDRESS);
+            assert_ok!(BridgeModule::set_contract_destination(Origin::ROOT, contract, true));
+            System::set_block_number(1);
+
+            assert_noop!(
+                BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, contract, TOKEN_ID, 10, false),
+                DispatchError::Other("Destination is a contract, set allow_contract to transfer anyway")
+            );
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                contract,
+                TOKEN_ID,
+                10,
+                true
+            ));
+
+            let message_id = BridgeModule::message_id_by_transfer_id(0);
+            assert!(bridge_events()
+                .contains(&RawEvent::ContractDestinationTransfer(message_id, contract)));
+        })
+    }
+
+    #[test]
+    fn config_diff_should_report_changed_limits() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_eq!(BridgeModule::config_diff(), ConfigDiff::default());
+            let genesis_limits = BridgeModule::current_limits();
+
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V1), None, 10, 20, 5, 40, 1));
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V2), None, 10, 20, 5, 40, 1));
+
+            let diff = BridgeModule::config_diff();
+            assert_eq!(
+                diff.limits,
+                Some((genesis_limits, BridgeModule::current_limits()))
+            );
+            assert_eq!(diff.validators, None);
+            assert_eq!(diff.quorum, None);
+        })
+    }
+
+    #[test]
+    fn disabled_first_day_hold_should_not_restrict_withdraw() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let mints = [(ETH_MESSAGE_ID1, TOKEN_ID), (ETH_MESSAGE_ID2, USDT_ID)];
+            for (message_id, token_id) in mints.iter() {
+                for v in [V1, V2].iter() {
+                    assert_ok!(BridgeModule::multi_signed_mint(
+                        Origin::signed(*v),
+                        CHAIN_ID,
+                        H256::from(*message_id),
+                        eth_address,
+                        USER2,
+                        *token_id,
+                        49
+                    ));
+                }
+            }
+            assert!(BridgeModule::first_day_hold_enabled(USDT_ID));
+            assert_ok!(BridgeModule::set_first_day_hold(Origin::ROOT, USDT_ID, false));
+
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                eth_address,
+                USDT_ID,
+                49,
+                false
+            ));
+            let usdt_id = BridgeModule::message_id_by_transfer_id(2);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), usdt_id));
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V2), usdt_id));
+            assert_eq!(BridgeModule::messages(usdt_id).status, Status::Approved);
+
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                48,
+                false
+            ));
+            let dai_id = BridgeModule::message_id_by_transfer_id(3);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), dai_id));
+            assert_eq!(
+                BridgeModule::approve_transfer(Origin::signed(V2), dai_id),
+                Err(DispatchError::Other(
+                    "Cannot withdraw more than the allowed share of first day deposit."
+                ))
+            );
+        })
+    }
+
+    #[test]
+    fn shrinking_validator_set_should_raise_alerts() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_message_id = H256::from(ETH_MESSAGE_ID);
+            let eth_message_id1 = H256::from(ETH_MESSAGE_ID1);
+            System::set_block_number(1);
+
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::update_validator_list(
+                    Origin::signed(*v),
+                    eth_message_id,
+                    2,
+                    vec![V1, V2, V3]
+                ));
+            }
+            assert!(bridge_events().contains(&RawEvent::ValidatorSetAtMinimum(3)));
+            assert!(!bridge_events().iter().any(|e| match e {
+                RawEvent::ValidatorSetBelowSafe(..) => true,
+                _ => false,
+            }));
+
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::update_validator_list(
+                    Origin::signed(*v),
+                    eth_message_id1,
+                    1,
+                    vec![V1, V2, V3]
+                ));
+            }
+            assert!(bridge_events().contains(&RawEvent::ValidatorSetBelowSafe(3, 1)));
+        })
+    }
+
+    #[test]
+    fn priority_account_should_bypass_pending_limit() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let saturating = [ETH_MESSAGE_ID1, ETH_MESSAGE_ID2, ETH_MESSAGE_ID3, ETH_MESSAGE_ID4];
+            for message_id in saturating.iter() {
+                assert_ok!(BridgeModule::multi_signed_mint(
+                    Origin::signed(V1),
+                    CHAIN_ID,
+                    H256::from(*message_id),
+                    eth_address,
+                    USER3,
+                    TOKEN_ID,
+                    99
+                ));
+            }
+            assert_eq!(BridgeModule::pending_mint_count(), 396);
+            assert_ok!(BridgeModule::set_priority_account(Origin::ROOT, USER1, true));
+
+            assert_noop!(
+                BridgeModule::multi_signed_mint(
+                    Origin::signed(V1),
+                    CHAIN_ID,
+                    H256::from(ETH_MESSAGE_ID5),
+                    eth_address,
+                    USER2,
+                    TOKEN_ID,
+                    99
+                ),
+                DispatchError::Other("Too many pending mint transactions.")
+            );
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                CHAIN_ID,
+                H256::from(ETH_MESSAGE_ID6),
+                eth_address,
+                USER1,
+                TOKEN_ID,
+                99
+            ));
+            assert_eq!(BridgeModule::pending_mint_count(), 495);
+        })
+    }
+
+    #[test]
+    fn would_exceed_daily_should_have_no_side_effects() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            System::set_block_number(1);
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                40,
+                false
+            ));
+            let events = System::events().len();
+
+            assert!(!BridgeModule::would_exceed_daily(TOKEN_ID, &USER2, 9));
+            assert!(BridgeModule::would_exceed_daily(TOKEN_ID, &USER2, 10));
+            assert!(BridgeModule::daily_blocked((TOKEN_ID, 0)).is_empty());
+            assert_eq!(System::events().len(), events);
+
+            // the real path still blocks the account
+            assert_eq!(
+                BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, 10, false),
+                Err(DispatchError::Other(
+                    "Transfer declined, user blocked due to daily volume limit."
+                ))
+            );
+            assert_eq!(BridgeModule::daily_blocked((TOKEN_ID, 0)), vec![USER2]);
+        })
+    }
+
+    #[test]
+    fn disabled_token_should_reject_new_transfers() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let message_id = H256::from(ETH_MESSAGE_ID);
+            assert_ok!(TokenModule::_mint(USDT_ID, USER2, 60));
+
+            assert_ok!(BridgeModule::set_token_transfers_enabled(Origin::ROOT, USDT_ID, false));
+            assert_eq!(TokenModule::balance_of((USDT_ID, USER2)), 60);
+
+            assert_noop!(
+                BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, USDT_ID, 10, false),
+                DispatchError::Other("Token transfers disabled.")
+            );
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                CHAIN_ID,
+                message_id,
+                eth_address,
+                USER2,
+                USDT_ID,
+                10
+            ));
+            assert_eq!(
+                BridgeModule::multi_signed_mint(
+                    Origin::signed(V2),
+                    CHAIN_ID,
+                    message_id,
+                    eth_address,
+                    USER2,
+                    USDT_ID,
+                    10
+                ),
+                Err(DispatchError::Other("Token transfers disabled."))
+            );
+            assert_eq!(TokenModule::balance_of((USDT_ID, USER2)), 60);
+
+            // other tokens are not affected
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                10,
+                false
+            ));
+        })
+    }
+
+    #[test]
+    fn burn_batch_hints_should_group_by_token_and_destination() {
+        ExtBuilder::default().build().execute_with(|| {
+            let first = H160::from(ETH_ADDRESS);
+            let second = H160::from_low_u64_be(42);
+            let transfers = [(USER1, first), (USER2, second), (USER3, first)];
+            for (i, (from, to)) in transfers.iter().enumerate() {
+                assert_ok!(BridgeModule::set_transfer(
+                    Origin::signed(*from),
+                    CHAIN_ID,
+                    *to,
+                    TOKEN_ID,
+                    10,
+                    false
+                ));
+                let message_id = BridgeModule::message_id_by_transfer_id(i as u64);
+                assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), message_id));
+                assert_ok!(BridgeModule::approve_transfer(Origin::signed(V2), message_id));
+            }
+            let id = |i| BridgeModule::message_id_by_transfer_id(i);
+
+            assert_eq!(
+                BridgeModule::burn_batch_hints(),
+                vec![
+                    (TOKEN_ID, first, vec![id(0), id(2)]),
+                    (TOKEN_ID, second, vec![id(1)]),
+                ]
+            );
+
+            // confirmed burns leave the batch
+            assert_ok!(BridgeModule::confirm_transfer(Origin::signed(V1), id(1)));
+            assert_eq!(
+                BridgeModule::burn_batch_hints(),
+                vec![(TOKEN_ID, first, vec![id(0), id(2)])]
+            );
+
+            // and so do canceled ones
+            assert_ok!(BridgeModule::cancel_transfer(Origin::signed(V1), id(0)));
+            assert_ok!(BridgeModule::cancel_transfer(Origin::signed(V2), id(0)));
+            assert_eq!(
+                BridgeModule::burn_batch_hints(),
+                vec![(TOKEN_ID, first, vec![id(2)])]
+            );
+            assert_eq!(BridgeModule::approved_withdraws(), vec![id(2)]);
+        })
+    }
+
+    #[test]
+    fn bridging_system_accounts_should_fail() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let message_id = H256::from(ETH_MESSAGE_ID);
+            assert_ok!(BridgeModule::set_reserve_account(Origin::ROOT, Some(USER1)));
+            assert_ok!(BridgeModule::set_fee_collector(Origin::ROOT, Some(USER3)));
+
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                CHAIN_ID,
+                message_id,
+                eth_address,
+                USER1,
+                TOKEN_ID,
+                10
+            ));
+            assert_eq!(
+                BridgeModule::multi_signed_mint(
+                    Origin::signed(V2),
+                    CHAIN_ID,
+                    message_id,
+                    eth_address,
+                    USER1,
+                    TOKEN_ID,
+                    10
+                ),
+                Err(DispatchError::Other("Cannot bridge to/from a system account."))
+            );
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER1)), 0);
+
+            assert_noop!(
+                BridgeModule::set_transfer(Origin::signed(USER3), CHAIN_ID, eth_address, TOKEN_ID, 10, false),
+                DispatchError::Other("Cannot bridge to/from a system account.")
+            );
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                10,
+                false
+            ));
+        })
+    }
+
+    #[test]
+    fn emergency_admin_should_force_pause() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_noop!(BridgeModule::force_pause(Origin::signed(USER1)), DispatchError::BadOrigin);
+            assert!(BridgeModule::bridge_is_operational());
+
+            assert_ok!(BridgeModule::force_pause(Origin::signed(USER9)));
+            assert!(!BridgeModule::bridge_is_operational());
+
+            assert_ok!(BridgeModule::add_emergency_admin(Origin::ROOT, USER1));
+            assert_ok!(BridgeModule::force_pause(Origin::signed(USER1)));
+            assert_ok!(BridgeModule::remove_emergency_admin(Origin::ROOT, USER1));
+            assert_noop!(BridgeModule::force_pause(Origin::signed(USER1)), DispatchError::BadOrigin);
+            assert_ok!(BridgeModule::force_pause(Origin::ROOT));
+        })
+    }
+
+    #[test]
+    fn restore_previous_limits_should_work() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_noop!(
+                BridgeModule::restore_previous_limits(Origin::ROOT),
+                DispatchError::Other("No previous limits to restore")
+            );
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::update_limits(Origin::signed(*v), None, 10, 20, 5, 40, 1));
+            }
+            let first = BridgeModule::current_limits();
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::update_limits(Origin::signed(*v), None, 30, 60, 15, 90, 2));
+            }
+            let second = BridgeModule::current_limits();
+            assert_eq!(BridgeModule::previous_limits(), Some(first.clone()));
+
+            assert_ok!(BridgeModule::restore_previous_limits(Origin::ROOT));
+            assert_eq!(BridgeModule::current_limits(), first);
+            // restoring again undoes the rollback
+            assert_eq!(BridgeModule::previous_limits(), Some(second));
+        })
+    }
+
+    #[test]
+    fn account_active_tokens_should_work() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            assert!(BridgeModule::account_active_tokens(USER2).is_empty());
+
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                10,
+                false
+            ));
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                11,
+                false
+            ));
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                CHAIN_ID,
+                H256::from(ETH_MESSAGE_ID),
+                eth_address,
+                USER2,
+                USDT_ID,
+                10
+            ));
+
+            assert_eq!(
+                BridgeModule::account_active_tokens(USER2),
+                vec![TOKEN_ID, USDT_ID]
+            );
+            assert!(BridgeModule::account_active_tokens(USER1).is_empty());
+        })
+    }
+
+    #[test]
+    fn only_first_sign_should_open_transfer() {
+        ExtBuilder::default().build().execute_with(|| {
+            let message_id = H256::from(ETH_MESSAGE_ID);
+            let eth_address = H160::from(ETH_ADDRESS);
+            let opened = || {
+                bridge_events()
+                    .into_iter()
+                    .filter(|e| match e {
+                        RawEvent::TransferOpened(..) => true,
+                        _ => false,
+                    })
+                    .collect::<Vec<_>>()
+            };
+            System::set_block_number(1);
+
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                CHAIN_ID,
+                message_id,
+                eth_address,
+                USER2,
+                TOKEN_ID,
+                10
+            ));
+            assert_eq!(
+                opened(),
+                vec![RawEvent::TransferOpened(0, message_id, Kind::Transfer)]
+            );
+
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V2),
+                CHAIN_ID,
+                message_id,
+                eth_address,
+                USER2,
+                TOKEN_ID,
+                10
+            ));
+            assert_eq!(opened().len(), 1);
+        })
+    }
+
+    #[test]
+    fn new_validator_should_wait_for_activation() {
+        ExtBuilder::default()
+            .validator_activation_delay(5)
+            .build()
+            .execute_with(|| {
+                let message_id = H256::from(ETH_MESSAGE_ID);
+                let eth_address = H160::from(ETH_ADDRESS);
+                System::set_block_number(1);
+
+                for v in [V1, V2].iter() {
+                    assert_ok!(BridgeModule::update_validator_list(
+                        Origin::signed(*v),
+                        H256::from(ETH_MESSAGE_ID1),
+                        3,
+                        vec![V1, V2, V3, V4]
+                    ));
+                }
+                assert_eq!(BridgeModule::validator_active_from(V4), 1);
+
+                System::set_block_number(5);
+                assert_eq!(
+                    BridgeModule::multi_signed_mint(
+                        Origin::signed(V4),
+                        CHAIN_ID,
+                        message_id,
+                        eth_address,
+                        USER2,
+                        TOKEN_ID,
+                        10
+                    ),
+                    Err(DispatchError::Other("Validator is not active yet"))
+                );
+                // genesis validators vote right away
+                assert_ok!(BridgeModule::multi_signed_mint(
+                    Origin::signed(V1),
+                    CHAIN_ID,
+                    message_id,
+                    eth_address,
+                    USER2,
+                    TOKEN_ID,
+                    10
+                ));
+                assert_ok!(BridgeModule::multi_signed_mint(
+                    Origin::signed(V2),
+                    CHAIN_ID,
+                    message_id,
+                    eth_address,
+                    USER2,
+                    TOKEN_ID,
+                    10
+                ));
+
+                System::set_block_number(6);
+                assert_ok!(BridgeModule::multi_signed_mint(
+                    Origin::signed(V4),
+                    CHAIN_ID,
+                    message_id,
+                    eth_address,
+                    USER2,
+                    TOKEN_ID,
+                    10
+                ));
+                assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 10);
+            })
+    }
+
+    #[test]
+    fn full_state_snapshot_should_paginate() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let messages = [ETH_MESSAGE_ID1, ETH_MESSAGE_ID2, ETH_MESSAGE_ID3];
+            for message_id in messages.iter() {
+                assert_ok!(BridgeModule::multi_signed_mint(
+                    Origin::signed(V1),
+                    CHAIN_ID,
+                    H256::from(*message_id),
+                    eth_address,
+                    USER2,
+                    TOKEN_ID,
+                    10
+                ));
+            }
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V1), None, 10, 20, 5, 40, 1));
+            assert_ok!(BridgeModule::pause_bridge(Origin::signed(V1)));
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V1), None, 10, 20, 5, 40, 2));
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V2), None, 10, 20, 5, 40, 2));
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V1), None, 10, 20, 5, 40, 3));
+
+            // closed proposals are left out
+            let mut ids = vec![];
+            let mut cursor = Some(0);
+            let mut pages = 0;
+            while let Some(start) = cursor {
+                let page = BridgeModule::full_state_snapshot(start, 2);
+                assert!(page.transfers.len() <= 2);
+                assert_eq!(page.pending_mint, 30);
+                ids.extend(page.transfers.iter().map(|(t, s)| (t.transfer_id, s.clone())));
+                cursor = page.next;
+                pages += 1;
+            }
+
+            assert_eq!(pages, 3);
+            assert_eq!(
+                ids,
+                vec![
+                    (0, Status::Pending),
+                    (1, Status::Pending),
+                    (2, Status::Pending),
+                    (3, Status::Pending),
+                    (4, Status::Pending),
+                    (6, Status::Pending),
+                ]
+            );
+            assert!(BridgeModule::full_state_snapshot(7, 2).transfers.is_empty());
+        })
+    }
+
+    #[test]
+    fn inverted_limits_should_fail() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_noop!(
+                BridgeModule::update_limits(Origin::signed(V1), None, 10, 20, 5, 40, 10),
+                "Minimum transaction value must be below maximum"
+            );
+            assert_noop!(
+                BridgeModule::update_limits(Origin::signed(V1), None, 10, 20, 5, 40, 11),
+                "Minimum transaction value must be below maximum"
+            );
+            assert_noop!(
+                BridgeModule::update_limits(Origin::signed(V1), None, 10, 20, 21, 40, 1),
+                "Daily limit for one address exceeds daily limit"
+            );
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V1), None, 10, 20, 20, 40, 1));
+        })
+    }
+
+    #[test]
+    fn stuck_pending_mint_should_expire() {
+        ExtBuilder::default()
+            .pending_mint_ttl(10)
+            .build()
+            .execute_with(|| {
+                System::set_block_number(1);
+                let message_id = H256::from(ETH_MESSAGE_ID);
+                let eth_address = H160::from(ETH_ADDRESS);
+
+                assert_ok!(BridgeModule::multi_signed_mint(
+                    Origin::signed(V2),
+                    CHAIN_ID,
+                    message_id,
+                    eth_address,
+                    USER2,
+                    TOKEN_ID,
+                    99
+                ));
+                assert_eq!(BridgeModule::pending_mint_count(), 99);
+                assert_eq!(BridgeModule::pending_mints_expiring_at(11), vec![0]);
+
+                run_to_block(11);
+                assert_eq!(BridgeModule::pending_mint_count(), 99);
+                run_to_block(12);
+
+                assert_eq!(BridgeModule::pending_mint_count(), 0);
+                assert_eq!(BridgeModule::messages(message_id).status, Status::Canceled);
+                assert!(!BridgeModule::transfers(0).open);
+                assert!(bridge_events().contains(&RawEvent::PendingMintExpired(message_id, TOKEN_ID)));
+                assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 0);
+
+                assert_noop!(
+                    BridgeModule::multi_signed_mint(
+                        Origin::signed(V1),
+                        CHAIN_ID,
+                        message_id,
+                        eth_address,
+                        USER2,
+                        TOKEN_ID,
+                        99
+                    ),
+                    "This transfer is not open"
+                );
+            })
+    }
+
+    #[test]
+    fn proposal_message_should_resolve_every_kind() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_message_id = H256::from(ETH_MESSAGE_ID);
+            let eth_address = H160::from(ETH_ADDRESS);
+            assert!(BridgeModule::proposal_message(0).is_none());
+
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                CHAIN_ID,
+                eth_message_id,
+                eth_address,
+                USER2,
+                TOKEN_ID,
+                99
+            ));
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V1), None, 10, 20, 5, 40, 1));
+            assert_ok!(BridgeModule::update_validator_list(
+                Origin::signed(V1),
+                H256::from(ETH_MESSAGE_ID1),
+                3,
+                vec![V1, V2, V3, V4]
+            ));
+            assert_ok!(BridgeModule::pause_bridge(Origin::signed(V1)));
+
+            match BridgeModule::proposal_message(0) {
+                Some(ProposalMessage::Transfer(m)) => {
+                    assert_eq!(m.message_id, eth_message_id);
+                    assert_eq!(m.amount, 99);
+                }
+                m => panic!("expected a transfer message, got {:?}", m),
+            }
+            match BridgeModule::proposal_message(1) {
+                Some(ProposalMessage::Limits(m)) => assert_eq!(m.limits.max_tx_value, 10),
+                m => panic!("expected a limit message, got {:?}", m),
+            }
+            match BridgeModule::proposal_message(2) {
+                Some(ProposalMessage::Validator(m)) => assert_eq!(m.accounts, vec![V1, V2, V3, V4]),
+                m => panic!("expected a validator message, got {:?}", m),
+            }
+            match BridgeModule::proposal_message(3) {
+                Some(ProposalMessage::Bridge(m)) => assert_eq!(m.action, Status::PauseTheBridge),
+                m => panic!("expected a bridge message, got {:?}", m),
+            }
+            let kinds = (0..4)
+                .filter_map(BridgeModule::proposal_message)
+                .map(|m| m.kind())
+                .collect::<Vec<_>>();
+            assert_eq!(kinds, vec![Kind::Transfer, Kind::Limits, Kind::Validator, Kind::Bridge]);
+            assert!(BridgeModule::proposal_message(4).is_none());
+        })
+    }
+
+    #[test]
+    fn config_at_should_rebuild_historical_config() {
+        ExtBuilder::default().build().execute_with(|| {
+            let genesis = BridgeModule::current_limits();
+
+            System::set_block_number(5);
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V1), None, 10, 20, 5, 40, 1));
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V2), None, 10, 20, 5, 40, 1));
+            let first_change = BridgeModule::current_limits();
+
+            System::set_block_number(10);
+            let eth_message_id = H256::from(ETH_MESSAGE_ID);
+            assert_ok!(BridgeModule::update_validator_list(
+                Origin::signed(V1),
+                eth_message_id,
+                3,
+                vec![V1, V2, V3, V4]
+            ));
+            assert_ok!(BridgeModule::update_validator_list(
+                Origin::signed(V2),
+                eth_message_id,
+                3,
+                vec![V1, V2, V3, V4]
+            ));
+
+            System::set_block_number(15);
+            for v in [V1, V2, V3].iter() {
+                assert_ok!(BridgeModule::update_limits(Origin::signed(*v), None, 30, 60, 15, 90, 2));
+            }
+
+            assert_eq!(
+                BridgeModule::config_at(3),
+                Some(BridgeConfigSnapshot {
+                    limits: genesis,
+                    validators_count: 3,
+                    quorum: 2,
+                })
+            );
+            assert_eq!(
+                BridgeModule::config_at(7),
+                Some(BridgeConfigSnapshot {
+                    limits: first_change.clone(),
+                    validators_count: 3,
+                    quorum: 2,
+                })
+            );
+            assert_eq!(
+                BridgeModule::config_at(12),
+                Some(BridgeConfigSnapshot {
+                    limits: first_change,
+                    validators_count: 4,
+                    quorum: 3,
+                })
+            );
+            assert_eq!(
+                BridgeModule::config_at(15).map(|c| c.limits),
+                Some(BridgeModule::current_limits())
+            );
+            assert_eq!(BridgeModule::config_at(16), None);
+        })
+    }
+
+    #[test]
+    fn quorum_resume_should_not_override_force_pause() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_noop!(
+                BridgeModule::force_resume(Origin::signed(USER9)),
+                DispatchError::Other("Bridge is not force-paused")
+            );
+            // resume proposal opened before the emergency stop
+            assert_ok!(BridgeModule::resume_bridge(Origin::signed(V1)));
+            assert_ok!(BridgeModule::force_pause(Origin::signed(USER9)));
+            assert!(BridgeModule::force_paused());
+
+            assert_noop!(
+                BridgeModule::resume_bridge(Origin::signed(V2)),
+                DispatchError::Other("Bridge is force-paused; requires force_resume.")
+            );
+            assert!(!BridgeModule::bridge_is_operational());
+
+            assert_noop!(BridgeModule::force_resume(Origin::signed(USER1)), DispatchError::BadOrigin);
+            assert_ok!(BridgeModule::force_resume(Origin::signed(USER9)));
+            assert!(BridgeModule::bridge_is_operational());
+            assert!(!BridgeModule::force_paused());
+        })
+    }
+
+    #[test]
+    fn recompute_pending_counters_should_fix_drift() {
+        ExtBuilder::default().build().execute_with(|| {
+            System::set_block_number(1);
+            let eth_address = H160::from(ETH_ADDRESS);
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                CHAIN_ID,
+                H256::from(ETH_MESSAGE_ID),
+                eth_address,
+                USER2,
+                TOKEN_ID,
+                99
+            ));
+            assert_ok!(TokenModule::_mint(TOKEN_ID, USER1, 100));
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER1),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                40,
+                false
+            ));
+            let burn_id = BridgeModule::message_id_by_transfer_id(1);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), burn_id));
+            assert_eq!(BridgeModule::pending_burn_count(), 40);
+            assert_eq!(BridgeModule::pending_mint_count(), 99);
+
+            <CurrentPendingBurn<Test>>::put(45);
+            <CurrentPendingMint<Test>>::put(7);
+
+            assert_noop!(
+                BridgeModule::recompute_pending_counters(Origin::signed(V1)),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(BridgeModule::recompute_pending_counters(Origin::ROOT));
+            assert_eq!(BridgeModule::pending_burn_count(), 40);
+            assert_eq!(BridgeModule::pending_mint_count(), 99);
+            assert!(bridge_events().contains(&RawEvent::PendingCountersReconciled(45, 40, 7, 99)));
+        })
+    }
+
+    #[test]
+    fn eth_address_volume_should_accrue_both_ways() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let message_id = H256::from(ETH_MESSAGE_ID);
+            assert_eq!(BridgeModule::eth_address_volume(eth_address), (0, 0));
+
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::multi_signed_mint(
+                    Origin::signed(*v),
+                    CHAIN_ID,
+                    message_id,
+                    eth_address,
+                    USER2,
+                    TOKEN_ID,
+                    49
+                ));
+            }
+            assert_eq!(BridgeModule::eth_address_volume(eth_address), (49, 0));
+
+            assert_ok!(TokenModule::_mint(TOKEN_ID, USER1, 100));
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER1),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                40,
+                false
+            ));
+            let sub_message_id = BridgeModule::message_id_by_transfer_id(1);
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::approve_transfer(Origin::signed(*v), sub_message_id));
+            }
+            // approval alone doesn't count, funds are only locked
+            assert_eq!(BridgeModule::eth_address_volume(eth_address), (49, 0));
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::confirm_transfer(Origin::signed(*v), sub_message_id));
+            }
+            assert_eq!(BridgeModule::eth_address_volume(eth_address), (49, 40));
+            assert_eq!(BridgeModule::eth_address_volume(H160::from(b"0x00b46c2526ebb8f4c0")), (0, 0));
+        })
+    }
+
+    #[test]
+    fn removed_validator_should_lose_its_rights() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let add_v4 = H256::from(ETH_MESSAGE_ID);
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::update_validator_list(
+                    Origin::signed(*v),
+                    add_v4,
+                    3,
+                    vec![V1, V2, V3, V4]
+                ));
+            }
+            assert_ok!(BridgeModule::check_validator(V4));
+
+            // V4 votes for a withdraw which stays open
+            assert_ok!(TokenModule::_mint(TOKEN_ID, USER1, 100));
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER1),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                40,
+                false
+            ));
+            let sub_message_id = BridgeModule::message_id_by_transfer_id(1);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V4), sub_message_id));
+            assert_eq!(BridgeModule::transfers(1).votes, 1);
+
+            let remove_v4 = H256::from(ETH_MESSAGE_ID1);
+            for v in [V1, V2, V3].iter() {
+                assert_ok!(BridgeModule::update_validator_list(
+                    Origin::signed(*v),
+                    remove_v4,
+                    2,
+                    vec![V1, V2, V3]
+                ));
+            }
+            assert_eq!(
+                BridgeModule::check_validator(V4),
+                Err("Only validators can call this function")
+            );
+            assert!(!BridgeModule::validators(V4));
+            assert_eq!(BridgeModule::validator_accounts(), vec![V1, V2, V3]);
+
+            // the stale vote no longer counts
+            assert_eq!(BridgeModule::open_proposals(), vec![1]);
+            assert_eq!(BridgeModule::transfers(1).votes, 0);
+            assert!(!BridgeModule::validator_votes((1, V4)));
+            assert_eq!(BridgeModule::messages(sub_message_id).status, Status::Withdraw);
+            assert_eq!(BridgeModule::pending_burn_count(), 0);
+            assert_noop!(
+                BridgeModule::approve_transfer(Origin::signed(V4), sub_message_id),
+                "Only validators can call this function"
+            );
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), sub_message_id));
+            assert_eq!(BridgeModule::messages(sub_message_id).status, Status::Pending);
+        })
+    }
+
+    #[test]
+    fn mint_should_be_approved_exactly_at_quorum() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let mint = |validator, message_id| {
+                BridgeModule::multi_signed_mint(
+                    Origin::signed(validator),
+                    CHAIN_ID,
+                    message_id,
+                    eth_address,
+                    USER2,
+                    TOKEN_ID,
+                    10,
+                )
+            };
+
+            // 3 validators, genesis quorum of 2
+            let first = H256::from(ETH_MESSAGE_ID);
+            assert_ok!(mint(V1, first));
+            assert_eq!(BridgeModule::messages(first).status, Status::Pending);
+            assert_ok!(mint(V2, first));
+            assert_eq!(BridgeModule::messages(first).status, Status::Confirmed);
+
+            // 4 validators, quorum of 3 set with the new list
+            let validators_id = H256::from(ETH_MESSAGE_ID1);
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::update_validator_list(
+                    Origin::signed(*v),
+                    validators_id,
+                    3,
+                    vec![V1, V2, V3, V4]
+                ));
+            }
+            assert_eq!(BridgeModule::quorum(), 3);
+            assert_eq!(BridgeModule::validators_count(), 4);
+
+            let second = H256::from(ETH_MESSAGE_ID2);
+            assert_ok!(mint(V1, second));
+            assert_ok!(mint(V2, second));
+            assert_eq!(BridgeModule::messages(second).status, Status::Pending);
+            assert_ok!(mint(V4, second));
+            assert_eq!(BridgeModule::messages(second).status, Status::Confirmed);
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 20);
+        })
+    }
+
+    #[test]
+    fn eth_receipt_should_be_attached_to_confirmed_burn() {
+        ExtBuilder::default().build().execute_with(|| {
+            System::set_block_number(1);
+            let eth_address = H160::from(ETH_ADDRESS);
+            let eth_tx = H256::from(ETH_MESSAGE_ID8);
+            assert_ok!(TokenModule::_mint(TOKEN_ID, USER1, 100));
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER1),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                40,
+                false
+            ));
+            let message_id = BridgeModule::message_id_by_transfer_id(0);
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::approve_transfer(Origin::signed(*v), message_id));
+            }
+            assert_noop!(
+                BridgeModule::attach_eth_receipt(Origin::signed(V1), message_id, eth_tx),
+                "Burn is not confirmed yet"
+            );
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::confirm_transfer(Origin::signed(*v), message_id));
+            }
+
+            assert_noop!(
+                BridgeModule::attach_eth_receipt(Origin::signed(USER1), message_id, eth_tx),
+                "Only validators can call this function"
+            );
+            assert_ok!(BridgeModule::set_receipt_requires_quorum(Origin::ROOT, true));
+            assert_ok!(BridgeModule::attach_eth_receipt(Origin::signed(V1), message_id, eth_tx));
+            assert_eq!(BridgeModule::burn_receipt(message_id), None);
+            assert_ok!(BridgeModule::attach_eth_receipt(Origin::signed(V2), message_id, eth_tx));
+
+            assert_eq!(BridgeModule::burn_receipt(message_id), Some(eth_tx));
+            assert!(bridge_events().contains(&RawEvent::BurnReceiptAttached(message_id, eth_tx)));
+            assert_noop!(
+                BridgeModule::attach_eth_receipt(Origin::signed(V3), message_id, eth_tx),
+                "Receipt is already attached"
+            );
+        })
+    }
+
+    #[test]
+    fn all_token_limits_should_list_every_token() {
+        ExtBuilder::default().build().execute_with(|| {
+            let genesis = BridgeModule::current_limits();
+            let token_ids = BridgeModule::all_token_limits()
+                .into_iter()
+                .map(|(id, limits)| {
+                    assert_eq!(limits, genesis);
+                    id
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(token_ids, vec![0, 1, 2, 3]);
+
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::update_limits(Origin::signed(*v), None, 10, 20, 5, 40, 1));
+            }
+            let updated = BridgeModule::current_limits();
+            assert!(BridgeModule::all_token_limits()
+                .iter()
+                .all(|(_, limits)| *limits == updated));
+        })
+    }
+
+    #[test]
+    fn limits_should_apply_per_token() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let shared = BridgeModule::current_limits();
+            assert_noop!(
+                BridgeModule::update_limits(Origin::signed(V1), Some(9), 10, 20, 5, 40, 1),
+                "Unknown token"
+            );
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::update_limits(Origin::signed(*v), Some(TOKEN_ID), 10, 20, 5, 40, 1));
+                assert_ok!(BridgeModule::update_limits(Origin::signed(*v), Some(USDT_ID), 30, 60, 15, 90, 2));
+            }
+            // the shared limits stay for the rest
+            assert_eq!(BridgeModule::current_limits(), shared);
+            assert_eq!(BridgeModule::limits_of(1), shared);
+
+            let all = BridgeModule::all_token_limits();
+            assert_eq!(all.len(), 4);
+            assert_eq!(all[0], (TOKEN_ID, BridgeModule::limits_of(TOKEN_ID)));
+            assert_eq!(all[0].1.max_tx_value, 10);
+            assert_eq!(all[2].1.max_tx_value, 30);
+            assert_eq!(all[2].1.min_tx_value, 2);
+            assert_eq!(all[1].1, shared);
+            assert_eq!(all[3].1, shared);
+
+            assert_ok!(TokenModule::_mint(TOKEN_ID, USER1, 100));
+            assert_ok!(TokenModule::_mint(USDT_ID, USER1, 100));
+            assert_noop!(
+                BridgeModule::set_transfer(Origin::signed(USER1), CHAIN_ID, eth_address, TOKEN_ID, 12, false),
+                "Invalid amount for transaction. Above the maximum limit."
+            );
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER1),
+                CHAIN_ID,
+                eth_address,
+                USDT_ID,
+                12,
+                false
+            ));
+        })
+    }
+
+    #[test]
+    fn stale_withdraw_should_expire() {
+        ExtBuilder::default()
+            .transfer_ttl(10)
+            .build()
+            .execute_with(|| {
+                System::set_block_number(1);
+                let eth_address = H160::from(ETH_ADDRESS);
+                assert_ok!(TokenModule::_mint(TOKEN_ID, USER1, 100));
+                assert_ok!(BridgeModule::set_transfer(
+                    Origin::signed(USER1),
+                    CHAIN_ID,
+                    eth_address,
+                    TOKEN_ID,
+                    40,
+                    false
+                ));
+                let message_id = BridgeModule::message_id_by_transfer_id(0);
+                assert_eq!(BridgeModule::transfer_expires_at(0), Some(11));
+                assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), message_id));
+                assert_eq!(BridgeModule::pending_burn_count(), 40);
+
+                run_to_block(11);
+                assert!(BridgeModule::transfers(0).open);
+                run_to_block(12);
+
+                assert!(!BridgeModule::transfers(0).open);
+                assert_eq!(BridgeModule::messages(message_id).status, Status::Canceled);
+                assert_eq!(BridgeModule::pending_burn_count(), 0);
+                assert_eq!(BridgeModule::daily_limits_by_account((TOKEN_ID, USER1)), 0);
+                assert_eq!(TokenModule::locked((TOKEN_ID, USER1)), 0);
+                assert_eq!(TokenModule::balance_of((TOKEN_ID, USER1)), 100);
+                assert!(bridge_events().contains(&RawEvent::ExpiredMessage(message_id, TOKEN_ID)));
+                assert_eq!(BridgeModule::transfer_expires_at(0), None);
+
+                assert_noop!(
+                    BridgeModule::approve_transfer(Origin::signed(V2), message_id),
+                    "This transfer is not open"
+                );
+            })
+    }
+
+    #[test]
+    fn daily_limits_should_wait_for_grace_period() {
+        ExtBuilder::default()
+            .daily_limit_grace_blocks(10)
+            .build()
+            .execute_with(|| {
+                System::set_block_number(1);
+                let eth_address = H160::from(ETH_ADDRESS);
+                assert_ok!(TokenModule::_mint(TOKEN_ID, USER2, 300));
+
+                // over the per-address daily limit of 50 within the grace
+                for _ in 0..2 {
+                    assert_ok!(BridgeModule::set_transfer(
+                        Origin::signed(USER2),
+                        CHAIN_ID,
+                        eth_address,
+                        TOKEN_ID,
+                        49,
+                        false
+                    ));
+                    TimestampModule::set_timestamp(TimestampModule::get() + 1);
+                }
+                assert!(!BridgeModule::would_exceed_daily(TOKEN_ID, &USER2, 49));
+                assert!(BridgeModule::daily_blocked((TOKEN_ID, 0)).is_empty());
+
+                run_to_block(10);
+                assert!(BridgeModule::would_exceed_daily(TOKEN_ID, &USER2, 10));
+                assert_eq!(
+                    BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, 10, false),
+                    Err(DispatchError::Other(
+                        "Transfer declined, user blocked due to daily volume limit."
+                    ))
+                );
+                assert_eq!(BridgeModule::daily_blocked((TOKEN_ID, 0)), vec![USER2]);
+            })
+    }
+
+    #[test]
+    fn daily_limit_overflow_should_fail_cleanly() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            assert_ok!(TokenModule::_mint(TOKEN_ID, USER2, 300));
+            <DailyLimits<Test>>::insert((TOKEN_ID, USER2), u128::max_value() - 5);
+
+            assert_noop!(
+                BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, 10, false),
+                "Overflow updating daily limit"
+            );
+            assert_eq!(BridgeModule::bridge_transfers_count(), 0);
+            assert_eq!(
+                BridgeModule::daily_limits_by_account((TOKEN_ID, USER2)),
+                u128::max_value() - 5
+            );
+        })
+    }
+
+    #[test]
+    fn confirm_transfer_batch_should_confirm_approved_burns() {
+        ExtBuilder::default().build().execute_with(|| {
+            System::set_block_number(1);
+            let eth_address = H160::from(ETH_ADDRESS);
+            assert_ok!(TokenModule::_mint(TOKEN_ID, USER1, 100));
+            assert_ok!(TokenModule::_mint(TOKEN_ID, USER2, 100));
+            for user in [USER1, USER2].iter() {
+                assert_ok!(BridgeModule::set_transfer(
+                    Origin::signed(*user),
+                    CHAIN_ID,
+                    eth_address,
+                    TOKEN_ID,
+                    20,
+                    false
+                ));
+            }
+            let first = BridgeModule::message_id_by_transfer_id(0);
+            let second = BridgeModule::message_id_by_transfer_id(1);
+            for message_id in [first, second].iter() {
+                for v in [V1, V2].iter() {
+                    assert_ok!(BridgeModule::approve_transfer(Origin::signed(*v), *message_id));
+                }
+            }
+            let unknown = H256::from(ETH_MESSAGE_ID);
+
+            assert_noop!(
+                BridgeModule::confirm_transfer_batch(
+                    Origin::signed(V1),
+                    vec![first, second, unknown, unknown]
+                ),
+                "Batch is too large"
+            );
+            assert_ok!(BridgeModule::confirm_transfer_batch(
+                Origin::signed(V1),
+                vec![first, second, unknown]
+            ));
+            assert!(bridge_events().contains(&RawEvent::BatchConfirmProcessed(2, 1)));
+            assert_eq!(BridgeModule::messages(first).status, Status::Confirmed);
+            assert_eq!(BridgeModule::transfers(0).votes, 1);
+
+            // already voted ones are skipped
+            assert_ok!(BridgeModule::confirm_transfer_batch(Origin::signed(V1), vec![first]));
+            assert!(bridge_events().contains(&RawEvent::BatchConfirmProcessed(0, 1)));
+
+            assert_ok!(BridgeModule::confirm_transfer_batch(
+                Origin::signed(V2),
+                vec![first, second]
+            ));
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER1)), 80);
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 80);
+            assert!(!BridgeModule::transfers(0).open);
+            assert!(!BridgeModule::transfers(1).open);
+        })
+    }
+
+    #[test]
+    fn processed_mint_should_not_be_replayed() {
+        ExtBuilder::default().build().execute_with(|| {
+            let message_id = H256::from(ETH_MESSAGE_ID);
+            let eth_address = H160::from(ETH_ADDRESS);
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                CHAIN_ID,
+                message_id,
+                eth_address,
+                USER2,
+                TOKEN_ID,
+                49
+            ));
+            assert!(!BridgeModule::is_processed_message(message_id));
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V2),
+                CHAIN_ID,
+                message_id,
+                eth_address,
+                USER2,
+                TOKEN_ID,
+                49
+            ));
+            assert_eq!(BridgeModule::messages(message_id).status, Status::Confirmed);
+            assert!(BridgeModule::is_processed_message(message_id));
+
+            for v in [V1, V3].iter() {
+                assert_noop!(
+                    BridgeModule::multi_signed_mint(
+                        Origin::signed(*v),
+                        CHAIN_ID,
+                        message_id,
+                        eth_address,
+                        USER2,
+                        TOKEN_ID,
+                        49
+                    ),
+                    "Message already processed"
+                );
+            }
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 49);
+        })
+    }
+
+    #[test]
+    fn compliance_check_should_block_rejected_account() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            assert_ok!(TokenModule::_mint(TOKEN_ID, SANCTIONED, 100));
+            assert_noop!(
+                BridgeModule::set_transfer(Origin::signed(SANCTIONED), CHAIN_ID, eth_address, TOKEN_ID, 10, false),
+                "Transfer rejected by compliance check"
+            );
+
+            let message_id = H256::from(ETH_MESSAGE_ID);
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                CHAIN_ID,
+                message_id,
+                eth_address,
+                SANCTIONED,
+                TOKEN_ID,
+                10
+            ));
+            assert_eq!(
+                BridgeModule::multi_signed_mint(
+                    Origin::signed(V2),
+                    CHAIN_ID,
+                    message_id,
+                    eth_address,
+                    SANCTIONED,
+                    TOKEN_ID,
+                    10
+                ),
+                Err(DispatchError::Other("Transfer rejected by compliance check"))
+            );
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, SANCTIONED)), 100);
+
+            // everyone else goes through
+            assert_ok!(TokenModule::_mint(TOKEN_ID, USER1, 100));
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER1),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                10,
+                false
+            ));
+        })
+    }
+
+    #[test]
+    fn pending_transfers_should_list_open_transfers_with_votes() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let _ = TokenModule::_mint(TOKEN_ID, USER2, 100);
+            let _ = TokenModule::_mint(TOKEN_ID, USER3, 100);
+
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                10,
+                false
+            ));
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER3),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                20,
+                false
+            ));
+            let first = BridgeModule::message_id_by_transfer_id(0);
+            let second = BridgeModule::message_id_by_transfer_id(1);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), first));
+
+            assert_eq!(
+                BridgeModule::pending_transfers(),
+                vec![
+                    (0, first, Status::Pending, 1, 2),
+                    (1, second, Status::Withdraw, 0, 2)
+                ]
+            );
+
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V2), first));
+            assert_eq!(
+                BridgeModule::pending_transfers(),
+                vec![(1, second, Status::Withdraw, 0, 2)]
+            );
+        })
+    }
+
+    #[test]
+    fn zero_eth_address_should_fail() {
+        ExtBuilder::default().build().execute_with(|| {
+            let _ = TokenModule::_mint(TOKEN_ID, USER2, 100);
+
+            assert_noop!(
+                BridgeModule::set_transfer(
+                    Origin::signed(USER2),
+                    CHAIN_ID,
+                    H160::zero(),
+                    TOKEN_ID,
+                    10,
+                    false
+                ),
+                "Invalid destination address"
+            );
+            assert_noop!(
+                BridgeModule::multi_signed_mint(
+                    Origin::signed(V1),
+                    CHAIN_ID,
+                    H256::from(ETH_MESSAGE_ID),
+                    H160::zero(),
+                    USER2,
+                    TOKEN_ID,
+                    10
+                ),
+                "Invalid source address"
+            );
+
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                H160::from(ETH_ADDRESS),
+                TOKEN_ID,
+                10,
+                false
+            ));
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                CHAIN_ID,
+                H256::from(ETH_MESSAGE_ID),
+                H160::from(ETH_ADDRESS),
+                USER2,
+                TOKEN_ID,
+                10
+            ));
+        })
+    }
+
+    #[test]
+    fn check_invariants_should_report_drift() {
+        ExtBuilder::default().build().execute_with(|| {
+            System::set_block_number(1);
+            let eth_address = H160::from(ETH_ADDRESS);
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                CHAIN_ID,
+                H256::from(ETH_MESSAGE_ID),
+                eth_address,
+                USER2,
+                TOKEN_ID,
+                99
+            ));
+            assert_ok!(TokenModule::_mint(TOKEN_ID, USER1, 100));
+            assert_ok!(TokenModule::_mint(TOKEN_ID, USER3, 100));
+            for user in [USER1, USER3].iter() {
+                assert_ok!(BridgeModule::set_transfer(
+                    Origin::signed(*user),
+                    CHAIN_ID,
+                    eth_address,
+                    TOKEN_ID,
+                    40,
+                    false
+                ));
+            }
+            let approved_id = BridgeModule::message_id_by_transfer_id(1);
+            let pending_id = BridgeModule::message_id_by_transfer_id(2);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), approved_id));
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V2), approved_id));
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), pending_id));
+
+            assert_noop!(
+                BridgeModule::check_invariants(Origin::signed(V1)),
+                DispatchError::BadOrigin
+            );
+            assert_eq!(BridgeModule::invariant_violations(), Ok(vec![]));
+            assert_ok!(BridgeModule::check_invariants(Origin::ROOT));
+
+            <CurrentPendingMint<Test>>::put(7);
+            assert_ok!(TokenModule::lock(TOKEN_ID, USER1, 10));
+            assert_eq!(
+                BridgeModule::invariant_violations(),
+                Ok(vec![
+                    "Pending mint counter differs from open deposits",
+                    "Locked funds differ from approved withdraws"
+                ])
+            );
+            assert_eq!(
+                BridgeModule::check_invariants(Origin::ROOT),
+                Err(DispatchError::Other("Bridge invariants violated"))
+            );
+            assert!(bridge_events().contains(&RawEvent::InvariantViolated(
+                b"Locked funds differ from approved withdraws".to_vec()
+            )));
+        })
+    }
+
+    #[test]
+    fn first_day_withdraw_limit_percent_should_be_configurable() {
+        ExtBuilder::default()
+            .first_day_withdraw_limit_percent(50)
+            .build()
+            .execute_with(|| {
+                let eth_address = H160::from(ETH_ADDRESS);
+                let deposits = [(USER2, ETH_MESSAGE_ID1), (USER3, ETH_MESSAGE_ID2)];
+                for (user, message_id) in deposits.iter() {
+                    for v in [V1, V2].iter() {
+                        assert_ok!(BridgeModule::multi_signed_mint(
+                            Origin::signed(*v),
+                            CHAIN_ID,
+                            H256::from(*message_id),
+                            eth_address,
+                            *user,
+                            TOKEN_ID,
+                            40
+                        ));
+                    }
+                }
+
+                // 60% of the fresh deposit
+                assert_ok!(BridgeModule::set_transfer(
+                    Origin::signed(USER2),
+                    CHAIN_ID,
+                    eth_address,
+                    TOKEN_ID,
+                    24,
+                    false
+                ));
+                // 40% of the fresh deposit
+                assert_ok!(BridgeModule::set_transfer(
+                    Origin::signed(USER3),
+                    CHAIN_ID,
+                    eth_address,
+                    TOKEN_ID,
+                    16,
+                    false
+                ));
+                let over_limit = BridgeModule::message_id_by_transfer_id(2);
+                let within_limit = BridgeModule::message_id_by_transfer_id(3);
+
+                assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), over_limit));
+                assert_eq!(
+                    BridgeModule::approve_transfer(Origin::signed(V2), over_limit),
+                    Err(DispatchError::Other(
+                        "Cannot withdraw more than the allowed share of first day deposit."
+                    ))
+                );
+                assert_eq!(BridgeModule::messages(over_limit).status, Status::Canceled);
+
+                assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), within_limit));
+                assert_ok!(BridgeModule::approve_transfer(Origin::signed(V2), within_limit));
+                assert_eq!(BridgeModule::messages(within_limit).status, Status::Approved);
+                assert_eq!(TokenModule::locked((TOKEN_ID, USER3)), 16);
+            })
+    }
+
+    #[test]
+    fn revoked_vote_should_allow_voting_again() {
+        ExtBuilder::default().build().execute_with(|| {
+            System::set_block_number(1);
+            let eth_address = H160::from(ETH_ADDRESS);
+            let _ = TokenModule::_mint(TOKEN_ID, USER2, 100);
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                40,
+                false
+            ));
+            let message_id = BridgeModule::message_id_by_transfer_id(0);
+
+            assert_noop!(
+                BridgeModule::revoke_vote(Origin::signed(V1), message_id),
+                "This validator has not voted."
+            );
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), message_id));
+            assert_eq!(BridgeModule::pending_burn_count(), 40);
+            assert_eq!(BridgeModule::messages(message_id).status, Status::Pending);
+
+            assert_ok!(BridgeModule::revoke_vote(Origin::signed(V1), message_id));
+            assert!(bridge_events().contains(&RawEvent::VoteRevoked(message_id, V1)));
+            assert_eq!(BridgeModule::transfers(0).votes, 0);
+            assert_eq!(BridgeModule::pending_burn_count(), 0);
+            assert_eq!(BridgeModule::messages(message_id).status, Status::Withdraw);
+            assert_eq!(BridgeModule::invariant_violations(), Ok(vec![]));
+
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), message_id));
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V2), message_id));
+            assert_eq!(BridgeModule::messages(message_id).status, Status::Approved);
+            assert_eq!(BridgeModule::pending_burn_count(), 0);
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 40);
+        })
+    }
+
+    #[test]
+    fn revoke_vote_after_quorum_should_fail() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let message_id = H256::from(ETH_MESSAGE_ID);
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::multi_signed_mint(
+                    Origin::signed(*v),
+                    CHAIN_ID,
+                    message_id,
+                    eth_address,
+                    USER2,
+                    TOKEN_ID,
+                    40
+                ));
+            }
+
+            assert_noop!(
+                BridgeModule::revoke_vote(Origin::signed(V1), message_id),
+                "This transfer is not open"
+            );
+            assert_noop!(
+                BridgeModule::revoke_vote(Origin::signed(V1), H256::from(ETH_MESSAGE_ID1)),
+                "Unknown message"
+            );
+            assert_eq!(BridgeModule::transfers(0).votes, 2);
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 40);
+        })
+    }
+
+    #[test]
+    fn hold_bypass_bond_should_allow_same_day_full_withdraw() {
+        ExtBuilder::default().build().execute_with(|| {
+            System::set_block_number(1);
+            let eth_address = H160::from(ETH_ADDRESS);
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::multi_signed_mint(
+                    Origin::signed(*v),
+                    CHAIN_ID,
+                    H256::from(ETH_MESSAGE_ID),
+                    eth_address,
+                    USER2,
+                    TOKEN_ID,
+                    49
+                ));
+            }
+
+            assert_ok!(BridgeModule::post_hold_bypass_bond(Origin::signed(USER2), TOKEN_ID));
+            assert_noop!(
+                BridgeModule::post_hold_bypass_bond(Origin::signed(USER2), TOKEN_ID),
+                "Hold bypass bond already posted"
+            );
+            assert_eq!(BridgeModule::hold_bypass_bond((TOKEN_ID, USER2)), Some(1_000));
+            assert_eq!(balances::Module::<Test>::reserved_balance(USER2), 1_000);
+
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                eth_address,
+                TOKEN_ID,
+                49,
+                false
+            ));
+            let message_id = BridgeModule::message_id_by_transfer_id(1);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), message_id));
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V2), message_id));
+            assert_eq!(BridgeModule::messages(message_id).status, Status::Approved);
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 49);
+
+            assert_noop!(
+                BridgeModule::slash_hold_bypass_bond(Origin::signed(V1), TOKEN_ID, USER2),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(BridgeModule::slash_hold_bypass_bond(Origin::ROOT, TOKEN_ID, USER2));
+            assert!(bridge_events().contains(&RawEvent::HoldBypassBondSlashed(USER2, TOKEN_ID, 1_000)));
+            assert_eq!(balances::Module::<Test>::reserved_balance(USER2), 0);
+            assert_eq!(balances::Module::<Test>::free_balance(USER2), 299_000);
+            assert_eq!(BridgeModule::hold_bypass_bond((TOKEN_ID, USER2)), None);
+            assert_noop!(
+                BridgeModule::release_hold_bypass_bond(Origin::signed(USER2), TOKEN_ID),
+                "No hold bypass bond posted"
+            );
+        })
+    }
+
+    #[test]
+    fn next_daily_reset_should_be_start_of_next_day() {
+        ExtBuilder::default().build().execute_with(|| {
+            let day = u64::from(DAY);
+            assert_eq!(BridgeModule::next_daily_reset(), day);
+
+            TimestampModule::set_timestamp(2 * day + day / 2);
+            assert_eq!(BridgeModule::next_daily_reset(), 3 * day);
+
+            TimestampModule::set_timestamp(3 * day);
+            assert_eq!(BridgeModule::next_daily_reset(), 4 * day);
+        })
+    }
+
+    #[test]
+    fn set_transfer_multi_should_open_a_transfer_per_token() {
+        ExtBuilder::default().build().execute_with(|| {
+            System::set_block_number(1);
+            let eth_address = H160::from(ETH_ADDRESS);
+            let _ = TokenModule::_mint(TOKEN_ID, USER2, 100);
+            let _ = TokenModule::_mint(USDT_ID, USER2, 100);
+
+            assert_ok!(BridgeModule::set_transfer_multi(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                eth_address,
+                vec![(TOKEN_ID, 10), (USDT_ID, 20)]
+            ));
+
+            assert_eq!(BridgeModule::bridge_transfers_count(), 2);
+            let dai = BridgeModule::messages(BridgeModule::message_id_by_transfer_id(0));
+            let usdt = BridgeModule::messages(BridgeModule::message_id_by_transfer_id(1));
+            assert_eq!((dai.token, dai.amount, dai.status), (TOKEN_ID, 10, Status::Withdraw));
+            assert_eq!((usdt.token, usdt.amount, usdt.status), (USDT_ID, 20, Status::Withdraw));
+            assert_eq!(BridgeModule::daily_limits_by_account((USDT_ID, USER2)), 20);
+            let relayed = bridge_events()
+                .into_iter()
+                .filter(|e| match e {
+                    RawEvent::RelayMessage(..) => true,
+                    _ => false,
+                })
+                .count();
+            assert_eq!(relayed, 2);
+        })
+    }
+
+    #[test]
+    fn set_transfer_multi_should_revert_on_any_invalid_item() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let _ = TokenModule::_mint(TOKEN_ID, USER2, 100);
+            let _ = TokenModule::_mint(USDT_ID, USER2, 200);
+
+            assert_noop!(
+                BridgeModule::set_transfer_multi(
+                    Origin::signed(USER2),
+                    CHAIN_ID,
+                    eth_address,
+                    vec![(TOKEN_ID, 10), (USDT_ID, 101)]
+                ),
+                "Invalid amount for transaction. Above the maximum limit."
+            );
+            assert_noop!(
+                BridgeModule::set_transfer_multi(
+                    Origin::signed(USER2),
+                    CHAIN_ID,
+                    eth_address,
+                    vec![(TOKEN_ID, 10), (TOKEN_ID, 20)]
+                ),
+                "Duplicate token in batch"
+            );
+            assert_noop!(
+                BridgeModule::set_transfer_multi(
+                    Origin::signed(USER2),
+                    CHAIN_ID,
+                    eth_address,
+                    vec![(0, 10), (1, 10), (2, 10), (3, 10)]
+                ),
+                "Batch is too large"
+            );
+            assert_eq!(BridgeModule::bridge_transfers_count(), 0);
+        })
+    }
+
+    #[test]
+    fn validator_change_should_apply_after_delay() {
+        ExtBuilder::default()
+            .validator_change_delay(5)
+            .build()
+            .execute_with(|| {
+                System::set_block_number(1);
+                let message_id = H256::from(ETH_MESSAGE_ID);
+                for v in [V1, V2].iter() {
+                    assert_ok!(BridgeModule::update_validator_list(
+                        Origin::signed(*v),
+                        message_id,
+                        3,
+                        vec![V1, V2, V3, V4]
+                    ));
+                }
+
+                assert!(bridge_events().contains(&RawEvent::ValidatorChangeScheduled(message_id, 6)));
+                assert_eq!(BridgeModule::validator_history(message_id).status, Status::Approved);
+                assert_eq!(BridgeModule::validators_count(), 3);
+                assert_eq!(BridgeModule::quorum(), 2);
+                assert_noop!(
+                    BridgeModule::update_validator_list(
+                        Origin::signed(V1),
+                        H256::from(ETH_MESSAGE_ID1),
+                        2,
+                        vec![V1, V2]
+                    ),
+                    "Validator set change already scheduled"
+                );
+
+                run_to_block(5);
+                assert_eq!(BridgeModule::validators_count(), 3);
+                assert!(BridgeModule::pending_validator_set().is_some());
+
+                run_to_block(6);
+                assert!(bridge_events().contains(&RawEvent::ValidatorChangeApplied(message_id)));
+                assert_eq!(BridgeModule::validator_history(message_id).status, Status::Confirmed);
+                assert_eq!(BridgeModule::validators_count(), 4);
+                assert_eq!(BridgeModule::quorum(), 3);
+                assert!(BridgeModule::pending_validator_set().is_none());
+            })
+    }
+
+    #[test]
+    fn scheduled_validator_change_should_be_cancelable_by_quorum() {
+        ExtBuilder::default()
+            .validator_change_delay(5)
+            .build()
+            .execute_with(|| {
+                System::set_block_number(1);
+                let message_id = H256::from(ETH_MESSAGE_ID);
+                assert_noop!(
+                    BridgeModule::cancel_validator_change(Origin::signed(V1)),
+                    "No validator set change scheduled"
+                );
+                for v in [V1, V2].iter() {
+                    assert_ok!(BridgeModule::update_validator_list(
+                        Origin::signed(*v),
+                        message_id,
+                        3,
+                        vec![V1, V2, V3, V4]
+                    ));
+                }
+
+                assert_ok!(BridgeModule::cancel_validator_change(Origin::signed(V2)));
+                assert!(BridgeModule::pending_validator_set().is_some());
+                assert_ok!(BridgeModule::cancel_validator_change(Origin::signed(V3)));
+                assert!(BridgeModule::pending_validator_set().is_none());
+                assert!(bridge_events().contains(&RawEvent::ValidatorChangeCanceled(message_id)));
+                assert_eq!(BridgeModule::validator_history(message_id).status, Status::Canceled);
+
+                run_to_block(10);
+                assert_eq!(BridgeModule::validators_count(), 3);
+                assert_eq!(BridgeModule::validator_accounts(), vec![V1, V2, V3]);
+            })
+    }
+
+    #[test]
+    fn temporary_limits_should_revert_after_window() {
+        ExtBuilder::default().build().execute_with(|| {
+            let base = BridgeModule::current_limits();
+            let elevated = Limits {
+                max_tx_value: 1_000,
+                day_max_limit: 2_000,
+                day_max_limit_for_one_address: 500,
+                max_pending_tx_limit: 4_000,
+                min_tx_value: 1,
+            };
+            assert_noop!(
+                BridgeModule::schedule_temporary_limits(Origin::signed(V1), elevated.clone(), 3, 5),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                BridgeModule::schedule_temporary_limits(Origin::ROOT, elevated.clone(), 5, 5),
+                "Temporary limits window is empty"
+            );
+            assert_noop!(
+                BridgeModule::schedule_temporary_limits(Origin::ROOT, elevated.clone(), 0, 5),
+                "Temporary limits must start in a future block"
+            );
+            assert_ok!(BridgeModule::schedule_temporary_limits(
+                Origin::ROOT,
+                elevated.clone(),
+                3,
+                5
+            ));
+            assert_noop!(
+                BridgeModule::schedule_temporary_limits(Origin::ROOT, elevated.clone(), 6, 8),
+                "Temporary limits already scheduled"
+            );
+
+            run_to_block(2);
+            assert_eq!(BridgeModule::current_limits(), base);
+
+            run_to_block(3);
+            assert_eq!(BridgeModule::current_limits(), elevated);
+            assert!(bridge_events().contains(&RawEvent::TemporaryLimitsActive(5)));
+            run_to_block(4);
+            assert_eq!(BridgeModule::current_limits(), elevated);
+
+            run_to_block(5);
+            assert_eq!(BridgeModule::current_limits(), base);
+            assert!(bridge_events().contains(&RawEvent::TemporaryLimitsExpired));
+            assert_eq!(BridgeModule::temporary_limits(), None);
+            assert_eq!(BridgeModule::limits_before_temporary(), None);
+        });
+    }
+
+    #[test]
+    fn simulate_transfer_should_name_the_declining_check() {
+        ExtBuilder::default()
+            .validator_activity_window(10)
+            .build()
+            .execute_with(|| {
+                let eth_address = H160::from(ETH_ADDRESS);
+                let simulate = |account, to, amount| BridgeModule::simulate_transfer(&account, to, TOKEN_ID, amount);
+                System::set_block_number(1);
+                assert_eq!(simulate(USER2, eth_address, 10), SimulatedOutcome::ValidatorsNotReady);
+            });
+
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let simulate = |account, to, amount| BridgeModule::simulate_transfer(&account, to, TOKEN_ID, amount);
+
+            assert_eq!(simulate(USER2, eth_address, 10), SimulatedOutcome::Accepted);
+            assert_eq!(simulate(USER2, H160::zero(), 10), SimulatedOutcome::InvalidDestination);
+            assert_eq!(simulate(SANCTIONED, eth_address, 10), SimulatedOutcome::ComplianceRejected);
+            assert_eq!(simulate(USER2, eth_address, 0), SimulatedOutcome::BelowMinimum);
+            assert_eq!(simulate(USER2, eth_address, 101), SimulatedOutcome::AboveMaximum);
+            assert_eq!(simulate(USER2, eth_address, 60), SimulatedOutcome::DailyLimitReached);
+
+            <CurrentPendingBurn<Test>>::put(395);
+            assert_eq!(simulate(USER2, eth_address, 10), SimulatedOutcome::PendingLimitReached);
+            <CurrentPendingBurn<Test>>::put(0);
+
+            assert_ok!(BridgeModule::set_contract_destination(Origin::ROOT, eth_address, true));
+            assert_eq!(simulate(USER2, eth_address, 10), SimulatedOutcome::ContractDestination);
+
+            assert_ok!(BridgeModule::set_reserve_account(Origin::ROOT, Some(USER2)));
+            assert_eq!(simulate(USER2, eth_address, 10), SimulatedOutcome::SystemAccount);
+
+            assert_ok!(BridgeModule::set_token_transfers_enabled(Origin::ROOT, TOKEN_ID, false));
+            assert_eq!(simulate(USER2, eth_address, 10), SimulatedOutcome::TokenTransfersDisabled);
+
+            assert_ok!(BridgeModule::force_pause(Origin::signed(USER9)));
+            assert_eq!(simulate(USER2, eth_address, 10), SimulatedOutcome::BridgeNotOperational);
+
+            // nothing was written along the way
+            assert_eq!(BridgeModule::daily_limits_by_account((TOKEN_ID, USER2)), 0);
+            assert_eq!(BridgeModule::bridge_transfers_count(), 0);
+        });
+    }
+
+    #[test]
+    fn rejected_transfers_should_be_counted_by_reason() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            System::set_block_number(1);
+
+            assert_eq!(
+                BridgeModule::simulate_transfer(&USER2, eth_address, TOKEN_ID, 60),
+                SimulatedOutcome::DailyLimitReached
+            );
+            assert_eq!(
+                BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, 60, false),
+                Err(DispatchError::Other(
+                    "Transfer declined, user blocked due to daily volume limit."
+                ))
+            );
+            assert_eq!(BridgeModule::rejections(RejectionReason::DailyLimit), 1);
+            assert!(bridge_events().contains(&RawEvent::TransferRejected(
+                USER2,
+                TOKEN_ID,
+                RejectionReason::DailyLimit
+            )));
+
+            <CurrentPendingBurn<Test>>::put(395);
+            assert_eq!(
+                BridgeModule::simulate_transfer(&USER1, eth_address, TOKEN_ID, 10),
+                SimulatedOutcome::PendingLimitReached
+            );
+            assert_eq!(
+                BridgeModule::set_transfer(Origin::signed(USER1), CHAIN_ID, eth_address, TOKEN_ID, 10, false),
+                Err(DispatchError::Other("Too many pending burn transactions."))
+            );
+            assert_eq!(BridgeModule::rejections(RejectionReason::PendingCapacity), 1);
+            assert_eq!(BridgeModule::rejections(RejectionReason::DailyLimit), 1);
+
+            <CurrentPendingBurn<Test>>::put(0);
+            assert_ok!(BridgeModule::set_transfer(Origin::signed(USER1), CHAIN_ID, eth_address, TOKEN_ID, 10, false));
+            assert_eq!(BridgeModule::rejections(RejectionReason::PendingCapacity), 1);
+        });
+    }
+
+    #[test]
+    fn rejected_batch_should_be_counted_once() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            System::set_block_number(1);
+            let _ = TokenModule::_mint(TOKEN_ID, USER2, 100);
+            let _ = TokenModule::_mint(USDT_ID, USER2, 100);
+            let rejected = || {
+                bridge_events()
+                    .into_iter()
+                    .filter(|e| match e {
+                        RawEvent::TransferRejected(..) => true,
+                        _ => false,
+                    })
+                    .count()
+            };
+
+            assert_eq!(
+                BridgeModule::set_transfer_multi(
+                    Origin::signed(USER2),
+                    CHAIN_ID,
+                    eth_address,
+                    vec![(TOKEN_ID, 10), (USDT_ID, 60)]
+                ),
+                Err(DispatchError::Other(
+                    "Transfer declined, user blocked due to daily volume limit."
+                ))
+            );
+            assert_eq!(BridgeModule::rejections(RejectionReason::DailyLimit), 1);
+            assert_eq!(rejected(), 1);
+            assert_eq!(BridgeModule::bridge_transfers_count(), 0);
+
+            assert_ok!(BridgeModule::set_transfer_multi(
+                Origin::signed(USER2),
+                CHAIN_ID,
+                eth_address,
+                vec![(TOKEN_ID, 10), (USDT_ID, 20)]
+            ));
+            assert_eq!(BridgeModule::rejections(RejectionReason::DailyLimit), 1);
+            assert_eq!(rejected(), 1);
+        });
+    }
+
+    #[test]
+    fn multi_signed_mint_batch_should_sign_every_deposit() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let mints = vec![
+                (H256::from(ETH_MESSAGE_ID1), eth_address, USER1, TOKEN_ID, 10),
+                (H256::from(ETH_MESSAGE_ID2), eth_address, USER2, TOKEN_ID, 20),
+                (H256::from(ETH_MESSAGE_ID3), eth_address, USER3, USDT_ID, 30),
+            ];
+
+            let mut too_large = mints.clone();
+            too_large.push((H256::from(ETH_MESSAGE_ID4), eth_address, USER4, TOKEN_ID, 40));
+            assert_noop!(
+                BridgeModule::multi_signed_mint_batch(Origin::signed(V1), CHAIN_ID, too_large),
+                "Batch is too large"
+            );
+            assert_noop!(
+                BridgeModule::multi_signed_mint_batch(
+                    Origin::signed(V1),
+                    CHAIN_ID,
+                    vec![mints[0].clone(), (H256::from(ETH_MESSAGE_ID4), eth_address, USER4, TOKEN_ID, 101)]
+                ),
+                "Invalid amount for transaction. Above the maximum limit."
+            );
+            assert_noop!(
+                BridgeModule::multi_signed_mint_batch(Origin::signed(V1), CHAIN_ID, vec![mints[0].clone(), mints[0].clone()]),
+                "Duplicate message in batch"
+            );
+            assert_noop!(
+                BridgeModule::multi_signed_mint_batch(Origin::signed(USER1), CHAIN_ID, mints.clone()),
+                "Only validators can call this function"
+            );
+
+            assert_ok!(BridgeModule::multi_signed_mint_batch(Origin::signed(V1), CHAIN_ID, mints.clone()));
+            assert_eq!(BridgeModule::bridge_transfers_count(), 3);
+            for (message_id, ..) in mints.iter() {
+                let transfer = BridgeModule::transfers(BridgeModule::transfer_id_by_hash(message_id));
+                assert_eq!(transfer.votes, 1);
+                assert!(transfer.open);
+            }
+            assert_noop!(
+                BridgeModule::multi_signed_mint_batch(Origin::signed(V1), CHAIN_ID, mints.clone()),
+                "This validator has already voted."
+            );
+
+            assert_ok!(BridgeModule::multi_signed_mint_batch(Origin::signed(V2), CHAIN_ID, mints));
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER1)), 10);
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 20);
+            assert_eq!(TokenModule::balance_of((USDT_ID, USER3)), 30);
+        });
+    }
+
+    #[test]
+    fn mint_should_require_fresh_price_when_enabled() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let usdt_message = H256::from(ETH_MESSAGE_ID1);
+            let dai_message = H256::from(ETH_MESSAGE_ID2);
+            System::set_block_number(1);
+
+            assert_noop!(
+                BridgeModule::set_require_fresh_price_for_mint(Origin::signed(V1), true),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(BridgeModule::set_require_fresh_price_for_mint(Origin::ROOT, true));
+
+            assert_ok!(BridgeModule::multi_signed_mint(Origin::signed(V1), CHAIN_ID, usdt_message, eth_address, USER1, USDT_ID, 10));
+            assert_eq!(
+                BridgeModule::multi_signed_mint(Origin::signed(V2), CHAIN_ID, usdt_message, eth_address, USER1, USDT_ID, 10),
+                Err(DispatchError::Other("No fresh price for the token"))
+            );
+            assert!(bridge_events().contains(&RawEvent::MintBlockedNoPrice(USDT_ID)));
+            assert_eq!(TokenModule::balance_of((USDT_ID, USER1)), 0);
+
+            assert_ok!(BridgeModule::multi_signed_mint(Origin::signed(V1), CHAIN_ID, dai_message, eth_address, USER1, TOKEN_ID, 10));
+            assert_ok!(BridgeModule::multi_signed_mint(Origin::signed(V2), CHAIN_ID, dai_message, eth_address, USER1, TOKEN_ID, 10));
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER1)), 10);
+        });
+    }
+
+    #[test]
+    fn joint_governance_update_should_apply_validators_and_limits_together() {
+        ExtBuilder::default().build().execute_with(|| {
+            let message_id = H256::from(ETH_MESSAGE_ID);
+            let validators = vec![V1, V2, V3, V4];
+            let limits = Limits {
+                max_tx_value: 1_000,
+                day_max_limit: 2_000,
+                day_max_limit_for_one_address: 500,
+                max_pending_tx_limit: 4_000,
+                min_tx_value: 1,
+            };
+            let invalid_limits = Limits {
+                min_tx_value: 1_000,
+                ..limits.clone()
+            };
+
+            assert_noop!(
+                BridgeModule::joint_governance_update(Origin::signed(V1), message_id, validators.clone(), 3, invalid_limits),
+                "Minimum transaction value must be below maximum"
+            );
+            assert_noop!(
+                BridgeModule::joint_governance_update(Origin::signed(V1), message_id, validators.clone(), 0, limits.clone()),
+                "Quorum can't be zero"
+            );
+
+            assert_ok!(BridgeModule::joint_governance_update(
+                Origin::signed(V1),
+                message_id,
+                validators.clone(),
+                3,
+                limits.clone()
+            ));
+            assert_eq!(BridgeModule::validator_accounts(), vec![V1, V2, V3]);
+            assert_eq!(BridgeModule::current_limits().max_tx_value, 100);
+
+            assert_ok!(BridgeModule::joint_governance_update(
+                Origin::signed(V2),
+                message_id,
+                validators.clone(),
+                3,
+                limits.clone()
+            ));
+            assert_eq!(BridgeModule::validator_accounts(), validators);
+            assert_eq!(BridgeModule::quorum(), 3);
+            assert_eq!(BridgeModule::current_limits(), limits);
+            assert_eq!(BridgeModule::joint_limits(message_id), None);
+        });
+    }
+
+    #[test]
+    fn relay_events_should_carry_token_contract() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let dai_contract = H160::from(DAI_CONTRACT);
+            System::set_block_number(1);
+            let _ = TokenModule::_mint(TOKEN_ID, USER2, 600);
+
+            assert_ok!(BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, 49, false));
+            let message_id = BridgeModule::message_id_by_transfer_id(0);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), message_id));
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V2), message_id));
+            assert!(bridge_events().contains(&RawEvent::ApprovedRelayMessage(
+                message_id,
+                CHAIN_ID,
+                TOKEN_ID,
+                dai_contract,
+                USER2,
+                eth_address,
+                49
+            )));
+
+            assert_ok!(BridgeModule::confirm_transfer(Origin::signed(V1), message_id));
+            assert_ok!(BridgeModule::confirm_transfer(Origin::signed(V2), message_id));
+            assert!(bridge_events().contains(&RawEvent::BurnedMessage(
+                message_id,
+                CHAIN_ID,
+                TOKEN_ID,
+                dai_contract,
+                USER2,
+                eth_address,
+                49
+            )));
+        });
+    }
+
+    #[test]
+    fn token_eth_address_should_change_on_quorum() {
+        ExtBuilder::default().build().execute_with(|| {
+            let usdt_contract = H160::from_low_u64_be(42);
+            System::set_block_number(1);
+            assert_eq!(BridgeModule::token_eth_address(TOKEN_ID), H160::from(DAI_CONTRACT));
+            assert_eq!(BridgeModule::token_eth_address(USDT_ID), H160::zero());
+
+            assert_noop!(
+                BridgeModule::update_token_eth_address(Origin::signed(V1), USDT_ID, H160::zero()),
+                "Invalid token address"
+            );
+            assert_noop!(
+                BridgeModule::update_token_eth_address(Origin::signed(V1), 42, usdt_contract),
+                "Unknown token"
+            );
+            assert_ok!(BridgeModule::update_token_eth_address(Origin::signed(V1), USDT_ID, usdt_contract));
+            assert_eq!(BridgeModule::token_eth_address(USDT_ID), H160::zero());
+            assert_ok!(BridgeModule::update_token_eth_address(Origin::signed(V2), USDT_ID, usdt_contract));
+            assert_eq!(BridgeModule::token_eth_address(USDT_ID), usdt_contract);
+            assert!(bridge_events().contains(&RawEvent::TokenAddressUpdated(USDT_ID, usdt_contract)));
+        });
+    }
+
+    #[test]
+    fn update_validator_list_weight_should_grow_with_the_list() {
+        let weight = |validators: Vec<u64>| {
+            Call::<Test>::update_validator_list(H256::from(ETH_MESSAGE_ID), 2, validators)
+                .get_dispatch_info()
+                .weight
+        };
+        assert!(weight(vec![V1, V2, V3, V4]) > weight(vec![V1, V2, V3]));
+        assert_eq!(
+            weight(vec![V1, V2, V3]),
+            <() as WeightInfo>::update_validator_list(3)
+        );
+        let batch = |ids: Vec<H256>| Call::<Test>::confirm_transfer_batch(ids).get_dispatch_info().weight;
+        assert!(batch(vec![H256::from(ETH_MESSAGE_ID); 2]) > batch(vec![H256::from(ETH_MESSAGE_ID)]));
+    }
+
+    #[test]
+    fn every_vote_should_emit_running_tally() {
+        ExtBuilder::default().build().execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(BridgeModule::pause_bridge(Origin::signed(V2)));
+            let transfer_id = BridgeModule::bridge_transfers_count() - 1;
+            assert_eq!(
+                bridge_events().last(),
+                Some(&RawEvent::ProposalVoted(transfer_id, V2, 1, 2))
+            );
+            assert_eq!(BridgeModule::bridge_is_operational(), true);
+
+            assert_ok!(BridgeModule::pause_bridge(Origin::signed(V1)));
+            assert!(bridge_events().contains(&RawEvent::ProposalVoted(transfer_id, V1, 2, 2)));
+            assert_eq!(BridgeModule::bridge_is_operational(), false);
+        })
+    }
+
+    #[test]
+    fn cancel_bridge_action_should_allow_a_new_pause() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_noop!(
+                BridgeModule::cancel_bridge_action(Origin::signed(V1)),
+                "No bridge action to cancel"
+            );
+            assert_ok!(BridgeModule::pause_bridge(Origin::signed(V2)));
+            assert_noop!(
+                BridgeModule::cancel_bridge_action(Origin::signed(USER1)),
+                "Only validators can call this function"
+            );
+            assert_ok!(BridgeModule::cancel_bridge_action(Origin::signed(V1)));
+            assert!(!BridgeModule::transfers(0).open);
+            assert_eq!(BridgeModule::bridge_is_operational(), true);
+
+            // a fresh proposal needs the full quorum again
+            assert_ok!(BridgeModule::pause_bridge(Origin::signed(V2)));
+            assert_eq!(BridgeModule::bridge_transfers_count(), 2);
+            assert_eq!(BridgeModule::transfers(1).votes, 1);
+            assert_eq!(BridgeModule::bridge_is_operational(), true);
+            assert_ok!(BridgeModule::pause_bridge(Origin::signed(V1)));
+            assert_eq!(BridgeModule::bridge_is_operational(), false);
+
+            // the pause is executed, only the resume could be canceled now
+            assert_ok!(BridgeModule::resume_bridge(Origin::signed(V1)));
+            assert_ok!(BridgeModule::resume_bridge(Origin::signed(V2)));
+            assert_noop!(
+                BridgeModule::cancel_bridge_action(Origin::signed(V1)),
+                "Bridge action is already confirmed"
+            );
+        })
+    }
+
+    #[test]
+    fn update_quorum_should_keep_validators() {
+        ExtBuilder::default().build().execute_with(|| {
+            let message_id = H256::from(ETH_MESSAGE_ID);
+            assert_noop!(
+                BridgeModule::update_quorum(Origin::signed(V1), message_id, 4),
+                "Quorum exceeds the number of validators"
+            );
+
+            assert_ok!(BridgeModule::update_quorum(Origin::signed(V1), message_id, 3));
+            assert_eq!(BridgeModule::quorum(), 2);
+            assert_ok!(BridgeModule::update_quorum(Origin::signed(V2), message_id, 3));
+            assert_eq!(BridgeModule::quorum(), 3);
+            assert_eq!(BridgeModule::validators_count(), 3);
+            assert_eq!(BridgeModule::validator_accounts(), vec![V1, V2, V3]);
+            assert_eq!(BridgeModule::validator_history(message_id).status, Status::Confirmed);
+
+            // two votes are not enough anymore
+            assert_ok!(BridgeModule::pause_bridge(Origin::signed(V1)));
+            assert_ok!(BridgeModule::pause_bridge(Origin::signed(V2)));
+            assert_eq!(BridgeModule::bridge_is_operational(), true);
+            assert_ok!(BridgeModule::pause_bridge(Origin::signed(V3)));
+            assert_eq!(BridgeModule::bridge_is_operational(), false);
+        })
+    }
+
+    #[test]
+    fn same_transfer_on_another_chain_should_be_distinct() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let message_id = H256::from(ETH_MESSAGE_ID);
+            System::set_block_number(1);
+            let _ = TokenModule::_mint(TOKEN_ID, USER2, 600);
+
+            assert_ok!(BridgeModule::set_transfer(Origin::signed(USER2), 1, eth_address, TOKEN_ID, 20, false));
+            assert_ok!(BridgeModule::set_transfer(Origin::signed(USER2), 10, eth_address, TOKEN_ID, 20, false));
+            assert_eq!(BridgeModule::bridge_transfers_count(), 2);
+            let mainnet = BridgeModule::message_id_by_transfer_id(0);
+            let l2 = BridgeModule::message_id_by_transfer_id(1);
+            assert_ne!(mainnet, l2);
+            assert_eq!(BridgeModule::messages(mainnet).chain_id, 1);
+            assert_eq!(BridgeModule::messages(l2).chain_id, 10);
+            assert!(bridge_events().contains(&RawEvent::RelayMessage(l2, 10)));
+
+            // a deposit is signed for the chain it was opened on only
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V1),
+                10,
+                message_id,
+                eth_address,
+                USER2,
+                TOKEN_ID,
+                40
+            ));
+            assert_noop!(
+                BridgeModule::multi_signed_mint(
+                    Origin::signed(V2),
+                    1,
+                    message_id,
+                    eth_address,
+                    USER2,
+                    TOKEN_ID,
+                    40
+                ),
+                "Message belongs to another chain"
+            );
+            assert_ok!(BridgeModule::multi_signed_mint(
+                Origin::signed(V2),
+                10,
+                message_id,
+                eth_address,
+                USER2,
+                TOKEN_ID,
+                40
+            ));
+            assert!(bridge_events().contains(&RawEvent::MintedMessage(message_id, 10, TOKEN_ID)));
+        })
+    }
+
+    #[test]
+    fn delayed_mint_should_execute_after_the_delay() {
+        ExtBuilder::default()
+            .mint_confirmation_delay(3)
+            .build()
+            .execute_with(|| {
+                let eth_address = H160::from(ETH_ADDRESS);
+                let message_id = H256::from(ETH_MESSAGE_ID);
+                run_to_block(1);
+                for v in [V1, V2].iter() {
+                    assert_ok!(BridgeModule::multi_signed_mint(
+                        Origin::signed(*v),
+                        CHAIN_ID,
+                        message_id,
+                        eth_address,
+                        USER2,
+                        TOKEN_ID,
+                        40
+                    ));
+                }
+                assert!(bridge_events().contains(&RawEvent::MintScheduled(message_id, 4)));
+                assert_eq!(BridgeModule::messages(message_id).status, Status::Approved);
+                assert_eq!(BridgeModule::pending_mint_count(), 40);
+                assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 0);
+
+                run_to_block(4);
+                assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 0);
+                run_to_block(5);
+                assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 40);
+                assert_eq!(BridgeModule::messages(message_id).status, Status::Confirmed);
+                assert_eq!(BridgeModule::pending_mint_count(), 0);
+                assert_eq!(BridgeModule::mint_execute_at(0), None);
+            })
+    }
+
+    #[test]
+    fn confirm_voters_of_canceled_burn_should_be_slashed() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            System::set_block_number(1);
+            let _ = TokenModule::_mint(TOKEN_ID, USER2, 600);
+            assert_noop!(
+                BridgeModule::set_slashing_enabled(Origin::signed(V1), true),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(BridgeModule::set_slashing_enabled(Origin::ROOT, true));
+
+            assert_ok!(BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, 40, false));
+            let message_id = BridgeModule::message_id_by_transfer_id(0);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), message_id));
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V2), message_id));
+            assert_ok!(BridgeModule::confirm_transfer(Origin::signed(V1), message_id));
+            assert_eq!(BridgeModule::confirm_voters(message_id), vec![V1]);
+
+            // the confirm vote of V1 doesn't count for the cancellation
+            assert_ok!(BridgeModule::cancel_transfer(Origin::signed(V2), message_id));
+            assert_eq!(BridgeModule::messages(message_id).status, Status::Canceled);
+            assert_eq!(BridgeModule::transfers(0).votes, 1);
+            assert_ok!(BridgeModule::cancel_transfer(Origin::signed(V3), message_id));
+
+            assert!(!BridgeModule::transfers(0).open);
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 0);
+            assert!(bridge_events().contains(&RawEvent::ValidatorSlashed(V1, 10_000)));
+            assert_eq!(Balances::free_balance(V1), 90_000);
+            assert_eq!(Balances::free_balance(V2), 100_000);
+            assert_eq!(Balances::free_balance(V3), 100_000);
+            assert!(BridgeModule::confirm_voters(message_id).is_empty());
+        })
+    }
+
+    #[test]
+    fn confirm_voters_of_executed_burn_should_be_cleared() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let _ = TokenModule::_mint(TOKEN_ID, USER2, 600);
+            assert_ok!(BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, 40, false));
+            let message_id = BridgeModule::message_id_by_transfer_id(0);
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::approve_transfer(Origin::signed(*v), message_id));
+            }
+            assert_ok!(BridgeModule::confirm_transfer(Origin::signed(V1), message_id));
+            assert_ok!(BridgeModule::confirm_transfer(Origin::signed(V2), message_id));
+
+            assert!(!BridgeModule::transfers(0).open);
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 560);
+            assert!(BridgeModule::confirm_voters(message_id).is_empty());
+        })
+    }
+
+    #[test]
+    fn validator_set_smaller_than_quorum_should_fail() {
+        ExtBuilder::default().build().execute_with(|| {
+            let message_id = H256::from(ETH_MESSAGE_ID);
+            assert_noop!(
+                BridgeModule::update_validator_list(Origin::signed(V1), message_id, 2, vec![V1]),
+                "Validator set too small for quorum"
+            );
+            assert_noop!(
+                BridgeModule::update_validator_list(Origin::signed(V1), message_id, 1, vec![]),
+                "Validator set too small for quorum"
+            );
+            assert_ok!(BridgeModule::update_validator_list(Origin::signed(V1), message_id, 1, vec![V1]));
+        })
+    }
+
+    #[test]
+    fn amount_limits_should_be_inclusive() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let mint = |message_id: &[u8; 32], amount| {
+                BridgeModule::multi_signed_mint(
+                    Origin::signed(V1),
+                    CHAIN_ID,
+                    H256::from(message_id),
+                    eth_address,
+                    USER2,
+                    TOKEN_ID,
+                    amount,
+                )
+            };
+            // limits are 1..=100
+            assert_ok!(mint(ETH_MESSAGE_ID, 100));
+            assert_ok!(mint(ETH_MESSAGE_ID1, 1));
+            assert_noop!(
+                mint(ETH_MESSAGE_ID2, 101),
+                "Invalid amount for transaction. Above the maximum limit."
+            );
+            assert_noop!(
+                mint(ETH_MESSAGE_ID2, 0),
+                "Invalid amount for transaction. Below the minimum limit."
+            );
+        })
+    }
+
+    #[test]
+    fn limits_change_should_be_announced_once_confirmed() {
+        ExtBuilder::default().build().execute_with(|| {
+            System::set_block_number(1);
+            let limits = Limits {
+                max_tx_value: 10,
+                day_max_limit: 20,
+                day_max_limit_for_one_address: 5,
+                max_pending_tx_limit: 40,
+                min_tx_value: 1,
+            };
+            let changes = || {
+                bridge_events()
+                    .into_iter()
+                    .filter(|e| match e {
+                        RawEvent::LimitsChanged(..) => true,
+                        _ => false,
+                    })
+                    .collect::<Vec<_>>()
+            };
+
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V2), None, 10, 20, 5, 40, 1));
+            assert!(changes().is_empty());
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V1), None, 10, 20, 5, 40, 1));
+
+            let message_id = BridgeModule::message_id_by_transfer_id(0);
+            assert_eq!(changes(), vec![RawEvent::LimitsChanged(message_id, limits)]);
+        })
+    }
+
+    #[test]
+    fn validator_list_with_duplicates_should_fail() {
+        ExtBuilder::default().build().execute_with(|| {
+            let message_id = H256::from(ETH_MESSAGE_ID);
+            assert_noop!(
+                BridgeModule::update_validator_list(Origin::signed(V1), message_id, 2, vec![V1, V1, V2]),
+                "Duplicate validator in list"
+            );
+
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::update_validator_list(
+                    Origin::signed(*v),
+                    message_id,
+                    2,
+                    vec![V1, V2, V4]
+                ));
+            }
+            assert_eq!(BridgeModule::validators_count(), 3);
+            assert_eq!(BridgeModule::validator_accounts(), vec![V1, V2, V4]);
+        })
+    }
+
+    #[test]
+    fn force_cancel_transfer_should_release_stuck_funds() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let _ = TokenModule::_mint(TOKEN_ID, USER2, 600);
+
+            // approved, funds are locked
+            assert_ok!(BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, 20, false));
+            let approved = BridgeModule::message_id_by_transfer_id(0);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), approved));
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V2), approved));
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 20);
+
+            // a single vote, the volume is pending
+            assert_ok!(BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, 25, false));
+            let pending = BridgeModule::message_id_by_transfer_id(1);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), pending));
+            assert_eq!(BridgeModule::pending_burn_count(), 25);
+            assert_eq!(BridgeModule::daily_limits_by_account((TOKEN_ID, USER2)), 45);
+
+            assert_noop!(
+                BridgeModule::force_cancel_transfer(Origin::signed(V1), approved),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(BridgeModule::force_cancel_transfer(Origin::ROOT, approved));
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 0);
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 600);
+            assert_eq!(BridgeModule::messages(approved).status, Status::Canceled);
+
+            assert_ok!(BridgeModule::force_cancel_transfer(Origin::ROOT, pending));
+            assert_eq!(BridgeModule::pending_burn_count(), 0);
+            assert!(!BridgeModule::transfers(1).open);
+            assert_eq!(BridgeModule::daily_limits_by_account((TOKEN_ID, USER2)), 0);
             assert_noop!(
-                BridgeModule::set_transfer(Origin::signed(USER1), eth_address, TOKEN_ID, amount2),
-                "Too many pending burn transactions."
+                BridgeModule::force_cancel_transfer(Origin::ROOT, pending),
+                "This transfer is already canceled"
             );
         })
     }
+
     #[test]
-    fn pending_mint_limit_should_work() {
+    fn force_cancel_transfer_should_not_undo_a_burn() {
         ExtBuilder::default().build().execute_with(|| {
-            let eth_message_id = H256::from(ETH_MESSAGE_ID);
-            let eth_message_id1 = H256::from(ETH_MESSAGE_ID1);
-            let eth_message_id2 = H256::from(ETH_MESSAGE_ID2);
-            let eth_message_id3 = H256::from(ETH_MESSAGE_ID3);
-            let eth_message_id4 = H256::from(ETH_MESSAGE_ID4);
-            let eth_message_id5 = H256::from(ETH_MESSAGE_ID5);
-            let eth_message_id6 = H256::from(ETH_MESSAGE_ID6);
-            let eth_message_id7 = H256::from(ETH_MESSAGE_ID7);
-            let eth_message_id8 = H256::from(ETH_MESSAGE_ID8);
             let eth_address = H160::from(ETH_ADDRESS);
-            let amount1 = 49;
+            let _ = TokenModule::_mint(TOKEN_ID, USER2, 600);
+            assert_ok!(BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, 20, false));
+            let message_id = BridgeModule::message_id_by_transfer_id(0);
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::approve_transfer(Origin::signed(*v), message_id));
+            }
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::confirm_transfer(Origin::signed(*v), message_id));
+            }
+            assert_noop!(
+                BridgeModule::force_cancel_transfer(Origin::ROOT, message_id),
+                "Failed to cancel. This transfer is already executed."
+            );
+        })
+    }
 
-            //substrate <----- ETH
+    #[test]
+    fn force_cancel_transfer_should_release_funds_under_eth_response_vote() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let _ = TokenModule::_mint(TOKEN_ID, USER2, 600);
+
+            // a single vote confirming the burn, the funds are still locked
+            assert_ok!(BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, 20, false));
+            let confirming = BridgeModule::message_id_by_transfer_id(0);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), confirming));
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V2), confirming));
+            assert_ok!(BridgeModule::confirm_transfer(Origin::signed(V1), confirming));
+            assert_eq!(BridgeModule::messages(confirming).status, Status::Confirmed);
+
+            // a single vote canceling the burn
+            assert_ok!(BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, 25, false));
+            let canceling = BridgeModule::message_id_by_transfer_id(1);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), canceling));
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V2), canceling));
+            assert_ok!(BridgeModule::cancel_transfer(Origin::signed(V1), canceling));
+            assert_eq!(BridgeModule::messages(canceling).status, Status::Canceled);
+
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 45);
+            assert_eq!(BridgeModule::daily_limits_by_account((TOKEN_ID, USER2)), 45);
+
+            assert_ok!(BridgeModule::force_cancel_transfer(Origin::ROOT, confirming));
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 25);
+            assert_eq!(BridgeModule::daily_limits_by_account((TOKEN_ID, USER2)), 25);
+            assert!(!BridgeModule::transfers(0).open);
+            assert!(BridgeModule::confirm_voters(confirming).is_empty());
+
+            assert_ok!(BridgeModule::force_cancel_transfer(Origin::ROOT, canceling));
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 0);
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 600);
+            assert_eq!(BridgeModule::daily_limits_by_account((TOKEN_ID, USER2)), 0);
+            assert_noop!(
+                BridgeModule::force_cancel_transfer(Origin::ROOT, canceling),
+                "This transfer is already canceled"
+            );
+        })
+    }
+
+    #[test]
+    fn force_cancel_transfer_should_release_withdraw_canceled_before_approval() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let _ = TokenModule::_mint(TOKEN_ID, USER2, 600);
+            assert_ok!(BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, 20, false));
+            let message_id = BridgeModule::message_id_by_transfer_id(0);
+            assert_ok!(BridgeModule::cancel_transfer(Origin::signed(V1), message_id));
+            assert_eq!(BridgeModule::messages(message_id).status, Status::Canceled);
+            assert!(BridgeModule::transfers(0).open);
+
+            assert_ok!(BridgeModule::force_cancel_transfer(Origin::ROOT, message_id));
+            assert!(!BridgeModule::transfers(0).open);
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 0);
+            assert_eq!(BridgeModule::pending_burn_count(), 0);
+            assert_eq!(BridgeModule::daily_limits_by_account((TOKEN_ID, USER2)), 0);
+            assert_noop!(
+                BridgeModule::force_cancel_transfer(Origin::ROOT, message_id),
+                "This transfer is already canceled"
+            );
+        })
+    }
+
+    #[test]
+    fn mints_above_daily_account_limit_should_be_declined() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            System::set_block_number(1);
+            for message_id in [ETH_MESSAGE_ID1, ETH_MESSAGE_ID2].iter() {
+                for v in [V1, V2].iter() {
+                    assert_ok!(BridgeModule::multi_signed_mint(
+                        Origin::signed(*v),
+                        CHAIN_ID,
+                        H256::from(*message_id),
+                        eth_address,
+                        USER2,
+                        TOKEN_ID,
+                        20
+                    ));
+                }
+            }
+            assert_eq!(BridgeModule::daily_mints_by_account((TOKEN_ID, USER2, 0)), 40);
+
+            // 60 minted today is above the 50 limit of the account
+            let message_id = H256::from(ETH_MESSAGE_ID3);
             assert_ok!(BridgeModule::multi_signed_mint(
-                Origin::signed(V2),
-                eth_message_id,
+                Origin::signed(V1),
+                CHAIN_ID,
+                message_id,
                 eth_address,
                 USER2,
                 TOKEN_ID,
-                amount1
+                20
             ));
+            assert_eq!(
+                BridgeModule::multi_signed_mint(
+                    Origin::signed(V2),
+                    CHAIN_ID,
+                    message_id,
+                    eth_address,
+                    USER2,
+                    TOKEN_ID,
+                    20
+                ),
+                Err(DispatchError::Other(
+                    "Mint declined, user blocked due to daily volume limit."
+                ))
+            );
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 40);
+            assert_eq!(BridgeModule::messages(message_id).status, Status::Pending);
+            assert_eq!(BridgeModule::daily_blocked((TOKEN_ID, 0)), vec![USER2]);
+
+            // other accounts still receive their deposits
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::multi_signed_mint(
+                    Origin::signed(*v),
+                    CHAIN_ID,
+                    H256::from(ETH_MESSAGE_ID4),
+                    eth_address,
+                    USER3,
+                    TOKEN_ID,
+                    20
+                ));
+            }
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER3)), 20);
 
-            //substrate <----- ETH
+            // the mint volume starts over the next day
+            TimestampModule::set_timestamp(u64::from(DAY));
             assert_ok!(BridgeModule::multi_signed_mint(
                 Origin::signed(V2),
-                eth_message_id2,
+                CHAIN_ID,
+                message_id,
                 eth_address,
-                USER3,
+                USER2,
                 TOKEN_ID,
-                amount1
+                20
             ));
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 60);
+            assert_eq!(BridgeModule::daily_mints_by_account((TOKEN_ID, USER2, 1)), 20);
+            assert_eq!(BridgeModule::daily_mints_by_account((TOKEN_ID, USER2, 0)), 0);
+        })
+    }
 
-            //substrate <----- ETH
-            assert_ok!(BridgeModule::multi_signed_mint(
-                Origin::signed(V2),
-                eth_message_id3,
-                eth_address,
-                USER4,
-                TOKEN_ID,
-                amount1
-            ));
+    #[test]
+    fn emergency_unlock_should_release_unfinished_withdraws() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            assert_ok!(TokenModule::_mint(TOKEN_ID, USER2, 100));
 
-            //substrate <----- ETH
-            assert_ok!(BridgeModule::multi_signed_mint(
-                Origin::signed(V2),
-                eth_message_id4,
-                eth_address,
-                USER5,
-                TOKEN_ID,
-                amount1
-            ));
-            //substrate <----- ETH
-            assert_ok!(BridgeModule::multi_signed_mint(
-                Origin::signed(V2),
-                eth_message_id5,
-                eth_address,
-                USER6,
-                TOKEN_ID,
-                amount1
-            ));
-            //substrate <----- ETH
-            assert_ok!(BridgeModule::multi_signed_mint(
-                Origin::signed(V2),
-                eth_message_id6,
-                eth_address,
-                USER7,
-                TOKEN_ID,
-                amount1
-            ));
-            //substrate <----- ETH
-            assert_ok!(BridgeModule::multi_signed_mint(
-                Origin::signed(V2),
-                eth_message_id7,
+            // no votes yet, only the daily volume is taken
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
                 eth_address,
-                USER8,
                 TOKEN_ID,
-                amount1
+                10,
+                false
             ));
-            //substrate <----- ETH
-            assert_ok!(BridgeModule::multi_signed_mint(
-                Origin::signed(V2),
-                eth_message_id8,
+            let unvoted = BridgeModule::message_id_by_transfer_id(0);
+
+            // approved with a single confirm vote, the funds are still locked
+            assert_ok!(BridgeModule::set_transfer(
+                Origin::signed(USER2),
+                CHAIN_ID,
                 eth_address,
-                USER9,
                 TOKEN_ID,
-                amount1
+                20,
+                false
             ));
-            assert_eq!(BridgeModule::pending_mint_count(), amount1 * 8);
-
-            //substrate <----- ETH
+            let confirming = BridgeModule::message_id_by_transfer_id(1);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), confirming));
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V2), confirming));
+            assert_ok!(BridgeModule::confirm_transfer(Origin::signed(V1), confirming));
+            assert_eq!(BridgeModule::messages(confirming).status, Status::Confirmed);
+            assert_eq!(BridgeModule::daily_limits_by_account((TOKEN_ID, USER2)), 30);
+
+            assert_ok!(BridgeModule::enable_emergency_exit(Origin::ROOT));
+            assert_ok!(BridgeModule::emergency_unlock(Origin::signed(USER2), unvoted));
+            assert!(!BridgeModule::transfers(0).open);
+            assert_eq!(BridgeModule::messages(unvoted).status, Status::Canceled);
+            assert_eq!(BridgeModule::daily_limits_by_account((TOKEN_ID, USER2)), 20);
+
+            assert_ok!(BridgeModule::emergency_unlock(Origin::signed(USER2), confirming));
+            assert!(!BridgeModule::transfers(1).open);
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 0);
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 100);
+            assert_eq!(BridgeModule::daily_limits_by_account((TOKEN_ID, USER2)), 0);
             assert_noop!(
-                BridgeModule::multi_signed_mint(
-                    Origin::signed(V2),
-                    eth_message_id1,
-                    eth_address,
-                    USER1,
-                    TOKEN_ID,
-                    amount1 + 5
-                ),
-                "Too many pending mint transactions."
+                BridgeModule::emergency_unlock(Origin::signed(USER2), confirming),
+                DispatchError::Other("This transfer has nothing to unlock")
             );
         })
     }
+
     #[test]
-    fn blocking_account_by_volume_should_work() {
+    fn emergency_unlock_should_release_withdraw_canceled_before_approval() {
         ExtBuilder::default().build().execute_with(|| {
             let eth_address = H160::from(ETH_ADDRESS);
-            let amount1 = 600;
-            let amount2 = 49;
-            let _ = TokenModule::_mint(TOKEN_ID, USER2, amount1);
-            assert_ok!(BridgeModule::set_transfer(
-                Origin::signed(USER2),
-                eth_address,
-                TOKEN_ID,
-                amount2
-            ));
-            let sub_message_id = BridgeModule::message_id_by_transfer_id(0);
-            assert_ok!(BridgeModule::approve_transfer(
-                Origin::signed(V1),
-                sub_message_id
-            ));
-            assert_ok!(BridgeModule::approve_transfer(
-                Origin::signed(V2),
-                sub_message_id
-            ));
+            assert_ok!(TokenModule::_mint(TOKEN_ID, USER2, 100));
+
+            // a cancel vote before any approval, nothing is locked
+            assert_ok!(BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, 10, false));
+            let unapproved = BridgeModule::message_id_by_transfer_id(0);
+            assert_ok!(BridgeModule::cancel_transfer(Origin::signed(V1), unapproved));
+            assert_eq!(BridgeModule::messages(unapproved).status, Status::Canceled);
+            assert_eq!(BridgeModule::canceled_from(unapproved), Some(Status::Withdraw));
+            assert!(BridgeModule::transfers(0).open);
+
+            // canceled while pending, only the pending volume is given back
+            assert_ok!(BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, 20, false));
+            let pending = BridgeModule::message_id_by_transfer_id(1);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), pending));
+            assert_eq!(BridgeModule::pending_burn_count(), 20);
+            assert_ok!(BridgeModule::cancel_transfer(Origin::signed(V2), pending));
+            assert_eq!(BridgeModule::messages(pending).status, Status::Canceled);
+            assert!(!BridgeModule::transfers(1).open);
+            assert_eq!(BridgeModule::pending_burn_count(), 0);
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 0);
+
+            assert_ok!(BridgeModule::enable_emergency_exit(Origin::ROOT));
+            assert_ok!(BridgeModule::emergency_unlock(Origin::signed(USER2), unapproved));
+            assert!(!BridgeModule::transfers(0).open);
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 0);
+            assert_eq!(BridgeModule::daily_limits_by_account((TOKEN_ID, USER2)), 20);
+            assert_eq!(BridgeModule::canceled_from(unapproved), None);
+        })
+    }
 
-            assert_eq!(
-                BridgeModule::set_transfer(Origin::signed(USER2), eth_address, TOKEN_ID, amount2),
-                Err(DispatchError::Other(
-                    "Transfer declined, user blocked due to daily volume limit."
-                ))
-            );
+    #[test]
+    fn emergency_unlock_should_release_concurrent_withdraws() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            assert_ok!(TokenModule::_mint(TOKEN_ID, USER2, 100));
+            for amount in [20, 25].iter() {
+                assert_ok!(BridgeModule::set_transfer(Origin::signed(USER2), CHAIN_ID, eth_address, TOKEN_ID, *amount, false));
+            }
+            let first = BridgeModule::message_id_by_transfer_id(0);
+            let second = BridgeModule::message_id_by_transfer_id(1);
+            for message_id in [first, second].iter() {
+                for v in [V1, V2].iter() {
+                    assert_ok!(BridgeModule::approve_transfer(Origin::signed(*v), *message_id));
+                }
+            }
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 45);
+
+            assert_ok!(BridgeModule::enable_emergency_exit(Origin::ROOT));
+            assert_ok!(BridgeModule::emergency_unlock(Origin::signed(USER2), first));
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 25);
+            assert_ok!(BridgeModule::emergency_unlock(Origin::signed(USER2), second));
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 0);
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 100);
+            assert_eq!(BridgeModule::daily_limits_by_account((TOKEN_ID, USER2)), 0);
         })
     }
+
     #[test]
-    fn blocked_account_unblocked_next_day_should_work() {
+    fn pending_volume_should_count_once_per_transfer() {
         ExtBuilder::default().build().execute_with(|| {
             let eth_address = H160::from(ETH_ADDRESS);
-            let amount1 = 600;
-            let amount2 = 49;
-            run_to_block(DAY_IN_BLOCKS.into());
+            for v in [V1, V2].iter() {
+                assert_ok!(BridgeModule::update_validator_list(
+                    Origin::signed(*v),
+                    H256::from(ETH_MESSAGE_ID),
+                    3,
+                    vec![V1, V2, V3, V4]
+                ));
+            }
+            assert_eq!(BridgeModule::quorum(), 3);
 
-            let _ = TokenModule::_mint(TOKEN_ID, USER2, amount1);
+            let _ = TokenModule::_mint(TOKEN_ID, USER2, 100);
             assert_ok!(BridgeModule::set_transfer(
                 Origin::signed(USER2),
+                CHAIN_ID,
                 eth_address,
                 TOKEN_ID,
-                amount2
-            ));
-            let sub_message_id = BridgeModule::message_id_by_transfer_id(0);
-            assert_ok!(BridgeModule::approve_transfer(
-                Origin::signed(V1),
-                sub_message_id
+                40,
+                false
             ));
-            assert_ok!(BridgeModule::approve_transfer(
-                Origin::signed(V2),
-                sub_message_id
+            let message_id = BridgeModule::message_id_by_transfer_id(1);
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V1), message_id));
+            assert_ok!(BridgeModule::approve_transfer(Origin::signed(V2), message_id));
+            assert_eq!(BridgeModule::pending_burn_count(), 40);
+
+            assert_ok!(BridgeModule::revoke_vote(Origin::signed(V2), message_id));
+            assert_eq!(BridgeModule::pending_burn_count(), 40);
+            assert_eq!(BridgeModule::messages(message_id).status, Status::Pending);
+            assert_ok!(BridgeModule::revoke_vote(Origin::signed(V1), message_id));
+            assert_eq!(BridgeModule::pending_burn_count(), 0);
+            assert_eq!(BridgeModule::messages(message_id).status, Status::Withdraw);
+            assert_eq!(BridgeModule::invariant_violations(), Ok(vec![]));
+
+            for v in [V1, V2, V3].iter() {
+                assert_ok!(BridgeModule::approve_transfer(Origin::signed(*v), message_id));
+            }
+            assert_eq!(BridgeModule::messages(message_id).status, Status::Approved);
+            assert_eq!(BridgeModule::pending_burn_count(), 0);
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 40);
+        })
+    }
+
+    #[test]
+    fn second_validator_change_should_not_replace_scheduled_one() {
+        ExtBuilder::default()
+            .validator_change_delay(5)
+            .build()
+            .execute_with(|| {
+                System::set_block_number(1);
+                let first = H256::from(ETH_MESSAGE_ID);
+                let second = H256::from(ETH_MESSAGE_ID1);
+                assert_ok!(BridgeModule::update_validator_list(
+                    Origin::signed(V1),
+                    first,
+                    3,
+                    vec![V1, V2, V3, V4]
+                ));
+                assert_ok!(BridgeModule::update_validator_list(
+                    Origin::signed(V1),
+                    second,
+                    2,
+                    vec![V1, V2, V4]
+                ));
+
+                assert_ok!(BridgeModule::update_validator_list(
+                    Origin::signed(V2),
+                    first,
+                    3,
+                    vec![V1, V2, V3, V4]
+                ));
+                assert_noop!(
+                    BridgeModule::update_validator_list(Origin::signed(V2), second, 2, vec![V1, V2, V4]),
+                    "Validator set change already scheduled"
+                );
+                assert_eq!(BridgeModule::pending_validator_set().map(|(m, _)| m.message_id), Some(first));
+                assert_eq!(BridgeModule::validator_history(second).status, Status::Pending);
+
+                run_to_block(6);
+                assert_eq!(BridgeModule::validator_accounts(), vec![V1, V2, V3, V4]);
+                assert_eq!(BridgeModule::quorum(), 3);
+            })
+    }
+
+    #[test]
+    fn limits_confirmed_during_temporary_window_should_apply_after_it() {
+        ExtBuilder::default().build().execute_with(|| {
+            let elevated = Limits {
+                max_tx_value: 1_000,
+                day_max_limit: 2_000,
+                day_max_limit_for_one_address: 500,
+                max_pending_tx_limit: 4_000,
+                min_tx_value: 1,
+            };
+            let lowered = Limits {
+                max_tx_value: 10,
+                day_max_limit: 20,
+                day_max_limit_for_one_address: 5,
+                max_pending_tx_limit: 40,
+                min_tx_value: 1,
+            };
+            assert_ok!(BridgeModule::schedule_temporary_limits(
+                Origin::ROOT,
+                elevated.clone(),
+                3,
+                6
             ));
-            assert_eq!(
-                BridgeModule::set_transfer(Origin::signed(USER2), eth_address, TOKEN_ID, amount2),
-                Err(DispatchError::Other(
-                    "Transfer declined, user blocked due to daily volume limit."
-                ))
+
+            run_to_block(3);
+            assert_eq!(BridgeModule::current_limits(), elevated);
+            run_to_block(4);
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V1), None, 10, 20, 5, 40, 1));
+            assert_ok!(BridgeModule::update_limits(Origin::signed(V2), None, 10, 20, 5, 40, 1));
+            assert_eq!(BridgeModule::current_limits(), elevated);
+            assert_eq!(BridgeModule::limits_before_temporary(), Some(lowered.clone()));
+
+            run_to_block(6);
+            assert_eq!(BridgeModule::current_limits(), lowered);
+            assert_eq!(BridgeModule::previous_limits(), Some(elevated.clone()));
+            assert_eq!(BridgeModule::config_at(4).map(|c| c.limits), Some(elevated));
+            assert_eq!(BridgeModule::config_at(6).map(|c| c.limits), Some(lowered));
+        });
+    }
+
+    #[test]
+    fn multi_signed_mint_batch_should_check_deposits_reaching_quorum() {
+        ExtBuilder::default().build().execute_with(|| {
+            let eth_address = H160::from(ETH_ADDRESS);
+            let mints = vec![
+                (H256::from(ETH_MESSAGE_ID1), eth_address, USER2, TOKEN_ID, 30),
+                (H256::from(ETH_MESSAGE_ID2), eth_address, USER2, TOKEN_ID, 30),
+                (H256::from(ETH_MESSAGE_ID3), eth_address, USER3, USDT_ID, 10),
+            ];
+            assert_ok!(BridgeModule::set_token_permissioned(Origin::ROOT, USDT_ID, true));
+
+            // first votes don't mint anything yet
+            assert_ok!(BridgeModule::multi_signed_mint_batch(Origin::signed(V1), CHAIN_ID, mints.clone()));
+
+            assert_noop!(
+                BridgeModule::multi_signed_mint_batch(Origin::signed(V2), CHAIN_ID, mints.clone()),
+                "Mint declined, user blocked due to daily volume limit."
+            );
+            assert_noop!(
+                BridgeModule::multi_signed_mint_batch(Origin::signed(V2), CHAIN_ID, mints[1..].to_vec()),
+                "Mint recipient is not allowlisted for this token"
             );
+            for transfer_id in 0..3 {
+                assert_eq!(BridgeModule::transfers(transfer_id).votes, 1);
+            }
 
-            //user added to blocked vec
-            let blocked_vec: Vec<u64> = vec![USER2];
-            assert_eq!(BridgeModule::daily_blocked((TOKEN_ID, 1)), blocked_vec);
+            assert_ok!(BridgeModule::set_mint_recipient(Origin::ROOT, USDT_ID, USER3, true));
+            assert_ok!(BridgeModule::multi_signed_mint_batch(Origin::signed(V2), CHAIN_ID, mints[1..].to_vec()));
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 30);
+            assert_eq!(TokenModule::balance_of((USDT_ID, USER3)), 10);
+        });
+    }
 
-            run_to_block((DAY_IN_BLOCKS * 2).into());
-            run_to_block((DAY_IN_BLOCKS * 3).into());
+    #[test]
+    fn quorum_change_should_wait_for_validator_change_delay() {
+        ExtBuilder::default()
+            .validator_change_delay(5)
+            .build()
+            .execute_with(|| {
+                System::set_block_number(1);
+                let message_id = H256::from(ETH_MESSAGE_ID);
+                assert_ok!(BridgeModule::update_quorum(Origin::signed(V1), message_id, 3));
+                assert_ok!(BridgeModule::update_quorum(Origin::signed(V2), message_id, 3));
+
+                assert!(bridge_events().contains(&RawEvent::ValidatorChangeScheduled(message_id, 6)));
+                assert_eq!(BridgeModule::validator_history(message_id).status, Status::Approved);
+                assert_eq!(BridgeModule::quorum(), 2);
+                assert_noop!(
+                    BridgeModule::update_quorum(Origin::signed(V1), H256::from(ETH_MESSAGE_ID1), 1),
+                    "Validator set change already scheduled"
+                );
 
-            //try again
-            assert_ok!(BridgeModule::set_transfer(
-                Origin::signed(USER2),
-                eth_address,
-                TOKEN_ID,
-                amount2
-            ));
-        })
+                run_to_block(6);
+                assert!(bridge_events().contains(&RawEvent::ValidatorChangeApplied(message_id)));
+                assert_eq!(BridgeModule::validator_history(message_id).status, Status::Confirmed);
+                assert_eq!(BridgeModule::quorum(), 3);
+                assert_eq!(BridgeModule::validator_accounts(), vec![V1, V2, V3]);
+            })
+    }
+
+    #[test]
+    fn failed_scheduled_mint_should_be_canceled() {
+        ExtBuilder::default()
+            .mint_confirmation_delay(3)
+            .build()
+            .execute_with(|| {
+                let eth_address = H160::from(ETH_ADDRESS);
+                let message_id = H256::from(ETH_MESSAGE_ID);
+                run_to_block(1);
+                for v in [V1, V2].iter() {
+                    assert_ok!(BridgeModule::multi_signed_mint(
+                        Origin::signed(*v),
+                        CHAIN_ID,
+                        message_id,
+                        eth_address,
+                        USER2,
+                        TOKEN_ID,
+                        40
+                    ));
+                }
+                assert_eq!(BridgeModule::pending_mint_count(), 40);
+                assert_ok!(BridgeModule::set_token_transfers_enabled(Origin::ROOT, TOKEN_ID, false));
+
+                run_to_block(5);
+                assert!(bridge_events().contains(&RawEvent::ScheduledMintFailed(message_id, TOKEN_ID)));
+                assert_eq!(BridgeModule::messages(message_id).status, Status::Canceled);
+                assert_eq!(BridgeModule::pending_mint_count(), 0);
+                assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 0);
+                assert_eq!(BridgeModule::invariant_violations(), Ok(vec![]));
+            })
+    }
+
+    #[test]
+    fn recompute_should_keep_scheduled_mints_pending() {
+        ExtBuilder::default()
+            .mint_confirmation_delay(3)
+            .build()
+            .execute_with(|| {
+                let eth_address = H160::from(ETH_ADDRESS);
+                let message_id = H256::from(ETH_MESSAGE_ID);
+                run_to_block(1);
+                for v in [V1, V2].iter() {
+                    assert_ok!(BridgeModule::multi_signed_mint(
+                        Origin::signed(*v),
+                        CHAIN_ID,
+                        message_id,
+                        eth_address,
+                        USER2,
+                        TOKEN_ID,
+                        40
+                    ));
+                }
+                assert_eq!(BridgeModule::invariant_violations(), Ok(vec![]));
+                assert_ok!(BridgeModule::recompute_pending_counters(Origin::ROOT));
+                assert_eq!(BridgeModule::pending_mint_count(), 40);
+
+                run_to_block(5);
+                assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 40);
+                assert_eq!(BridgeModule::pending_mint_count(), 0);
+            })
     }
 }
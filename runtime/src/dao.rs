@@ -791,8 +791,39 @@ mod tests {
     impl token::Trait for Test {
         type Event = ();
     }
+    parameter_types! {
+        pub const ValidatorActivityWindow: BlockNumber = 0;
+        pub const MinValidators: u32 = 3;
+        pub const ValidatorActivationDelay: BlockNumber = 0;
+        pub const PendingMintTtl: BlockNumber = 0;
+        pub const TransferTtl: BlockNumber = 0;
+        pub const DailyLimitGraceBlocks: BlockNumber = 0;
+        pub const MaxBatchSize: u32 = 16;
+        pub const FirstDayWithdrawLimitPercent: u32 = 75;
+        pub const HoldBypassBond: Balance = 1_000;
+        pub const ValidatorChangeDelay: BlockNumber = 0;
+        pub const MaxMintBatch: u32 = 16;
+        pub const MintConfirmationDelay: BlockNumber = 0;
+        pub const SlashFraction: Perbill = Perbill::from_percent(1);
+    }
     impl bridge::Trait for Test {
         type Event = ();
+        type ValidatorActivityWindow = ValidatorActivityWindow;
+        type MinValidators = MinValidators;
+        type ValidatorActivationDelay = ValidatorActivationDelay;
+        type PendingMintTtl = PendingMintTtl;
+        type TransferTtl = TransferTtl;
+        type DailyLimitGraceBlocks = DailyLimitGraceBlocks;
+        type MaxBatchSize = MaxBatchSize;
+        type FirstDayWithdrawLimitPercent = FirstDayWithdrawLimitPercent;
+        type HoldBypassBond = HoldBypassBond;
+        type ValidatorChangeDelay = ValidatorChangeDelay;
+        type MaxMintBatch = MaxMintBatch;
+        type MintConfirmationDelay = MintConfirmationDelay;
+        type SlashFraction = SlashFraction;
+        type ComplianceCheck = ();
+        type TokenPrice = ();
+        type WeightInfo = ();
     }
 
     pub type Extrinsic = TestXt<Call, ()>;
@@ -802,6 +833,10 @@ mod tests {
     parameter_types! {
         pub const BlockFetchPeriod: BlockNumber = 2;
         pub const GracePeriod: BlockNumber = 5;
+        pub const MaxTrackedTokens: u32 = 2;
+        pub const OutlierThresholdPercent: u32 = 50;
+        pub const PriceHistoryDepth: u32 = 10;
+        pub const PriceWarmupPoints: u32 = 0;
     }
 
     impl price_oracle::Trait for Test {
@@ -813,6 +848,11 @@ mod tests {
         //   Then you need to manucally kickoff pricefetch
         type GracePeriod = GracePeriod;
         type BlockFetchPeriod = BlockFetchPeriod;
+        type MaxTrackedTokens = MaxTrackedTokens;
+        type EmergencyOrigin = system::EnsureRoot<u64>;
+        type OutlierThresholdPercent = OutlierThresholdPercent;
+        type PriceHistoryDepth = PriceHistoryDepth;
+        type PriceWarmupPoints = PriceWarmupPoints;
     }
 
     impl Trait for Test {
@@ -850,6 +890,7 @@ mod tests {
     const VERY_SMALL_VOTE_TIMEOUT: u32 = MINIMUM_VOTE_TIOMEOUT - 1;
     const VERY_BIG_VOTE_TIMEOUT: u32 = MAXIMUM_VOTE_TIMEOUT + 1;
     const TOKEN_ID: TokenId = 0;
+    const CHAIN_ID: ChainId = 1;
     const PROPOSAL_ID: ProposalId = 0;
     const YES: bool = true;
     const NO: bool = false;
@@ -896,6 +937,7 @@ mod tests {
             let _ = bridge::GenesisConfig::<Test> {
                 validators_count: 3u32,
                 validator_accounts: vec![V1, V2, V3],
+                emergency_admins: vec![],
                 current_limits: vec![
                     100 * 10u128.pow(18),
                     200 * 10u128.pow(18),
@@ -903,6 +945,8 @@ mod tests {
                     400 * 10u128.pow(18),
                     10 * 10u128.pow(18),
                 ],
+                token_limits: vec![],
+                token_eth_addresses: vec![],
             }
             .assimilate_storage(&mut storage);
 
@@ -2421,9 +2465,11 @@ mod tests {
             // substrate ----> ETH
             assert_ok!(BridgeModule::set_transfer(
                 Origin::signed(USER2),
+                CHAIN_ID,
                 eth_address,
                 TOKEN_ID,
-                token_amount
+                token_amount,
+                false
             ));
             // RelayMessage(message_id) event emitted
 
@@ -2,6 +2,7 @@ use sp_runtime::traits::{Convert, Saturating};
 use sp_runtime::{Fixed64, Perbill};
 use frame_support::{traits::{OnUnbalanced, Currency, Get}, weights::Weight};
 use crate::{Balances, Balance, System, Authorship, MaximumBlockWeight, NegativeImbalance};
+use crate::{bridge::TokenPrice, types::TokenId, MintPriceMaxAge, PriceOracle, Token};
 
 pub struct Author;
 impl OnUnbalanced<NegativeImbalance> for Author {
@@ -93,6 +94,17 @@ impl<T: Get<Perbill>> Convert<Fixed64, Fixed64> for TargetedFeeAdjustment<T> {
 	}
 }
 
+/// Fresh price of a bridged token is an oracle aggregate of its symbol
+/// recorded within `MintPriceMaxAge`, once the token has warmed up.
+pub struct OracleTokenPrice;
+impl TokenPrice for OracleTokenPrice {
+	fn has_fresh_price(token_id: TokenId) -> bool {
+		let symbol = Token::token_symbol_by_id(token_id);
+		!PriceOracle::is_warming_up(&symbol)
+			&& PriceOracle::has_fresh_aggregate(&symbol, MintPriceMaxAge::get())
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -342,4 +354,22 @@ mod tests {
 				})
 			});
 	}
+
+	#[test]
+	fn warming_up_token_has_no_fresh_price() {
+		use frame_support::storage::StorageMap;
+		let mut t: sp_io::TestExternalities =
+			system::GenesisConfig::default().build_storage::<Runtime>().unwrap().into();
+		t.execute_with(|| {
+			let symbol = b"DAI".to_vec();
+			crate::token::TokenSymbol::insert(0, symbol.clone());
+			crate::price_oracle::AggregatedPrices::<Runtime>::insert(&symbol, (0, 1_000));
+
+			crate::price_oracle::ObservedPoints::insert(&symbol, crate::PriceWarmupPoints::get() - 1);
+			assert!(!OracleTokenPrice::has_fresh_price(0));
+
+			crate::price_oracle::ObservedPoints::insert(&symbol, crate::PriceWarmupPoints::get());
+			assert!(OracleTokenPrice::has_fresh_price(0));
+		});
+	}
 }
\ No newline at end of file
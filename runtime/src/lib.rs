@@ -66,6 +66,9 @@ pub use bridge::Call as BridgeCall;
 
 mod price_oracle;
 
+/// Runtime APIs of the Akropolis pallets.
+pub mod api;
+
 /// Alias to 512-bit hash when used in the context of a transaction signature on the chain.
 pub type Signature = MultiSignature;
 
@@ -670,8 +673,41 @@ impl pallet_vesting::Trait for Runtime {
     type MinVestedTransfer = MinVestedTransfer;
 }
 
+parameter_types! {
+    pub const ValidatorActivityWindow: BlockNumber = DAYS;
+    pub const MinValidators: u32 = 3;
+    pub const ValidatorActivationDelay: BlockNumber = HOURS;
+    pub const PendingMintTtl: BlockNumber = DAYS;
+    pub const TransferTtl: BlockNumber = DAYS;
+    pub const DailyLimitGraceBlocks: BlockNumber = HOURS;
+    pub const MaxBatchSize: u32 = 64;
+    pub const FirstDayWithdrawLimitPercent: u32 = 75;
+    pub const HoldBypassBond: Balance = 100 * DOLLARS;
+    pub const ValidatorChangeDelay: BlockNumber = DAYS;
+    pub const MaxMintBatch: u32 = 32;
+    pub const MintConfirmationDelay: BlockNumber = 0;
+    pub const SlashFraction: Perbill = Perbill::from_percent(1);
+    pub const MintPriceMaxAge: Moment = HOURS as Moment * MILLISECS_PER_BLOCK;
+}
+
 impl bridge::Trait for Runtime {
     type Event = Event;
+    type ValidatorActivityWindow = ValidatorActivityWindow;
+    type MinValidators = MinValidators;
+    type ValidatorActivationDelay = ValidatorActivationDelay;
+    type PendingMintTtl = PendingMintTtl;
+    type TransferTtl = TransferTtl;
+    type DailyLimitGraceBlocks = DailyLimitGraceBlocks;
+    type MaxBatchSize = MaxBatchSize;
+    type FirstDayWithdrawLimitPercent = FirstDayWithdrawLimitPercent;
+    type HoldBypassBond = HoldBypassBond;
+    type ValidatorChangeDelay = ValidatorChangeDelay;
+    type MaxMintBatch = MaxMintBatch;
+    type MintConfirmationDelay = MintConfirmationDelay;
+    type SlashFraction = SlashFraction;
+    type ComplianceCheck = ();
+    type TokenPrice = impls::OracleTokenPrice;
+    type WeightInfo = ();
 }
 
 impl dao::Trait for Runtime {
@@ -696,6 +732,10 @@ type SubmitPricefetchTransaction = system::offchain::TransactionSubmitter<
 parameter_types! {
     pub const BlockFetchPeriod: BlockNumber = 2;
     pub const GracePeriod: BlockNumber = 5;
+    pub const MaxTrackedTokens: u32 = 16;
+    pub const OutlierThresholdPercent: u32 = 10;
+    pub const PriceHistoryDepth: u32 = 10;
+    pub const PriceWarmupPoints: u32 = 5;
 }
 
 impl price_oracle::Trait for Runtime {
@@ -704,6 +744,11 @@ impl price_oracle::Trait for Runtime {
     type SubmitUnsignedTransaction = SubmitPricefetchTransaction;
     type BlockFetchPeriod = BlockFetchPeriod;
     type GracePeriod = GracePeriod;
+    type MaxTrackedTokens = MaxTrackedTokens;
+    type EmergencyOrigin = bridge::EnsureEmergencyAdmin<Runtime>;
+    type OutlierThresholdPercent = OutlierThresholdPercent;
+    type PriceHistoryDepth = PriceHistoryDepth;
+    type PriceWarmupPoints = PriceWarmupPoints;
 }
 
 construct_runtime!(
@@ -745,7 +790,7 @@ construct_runtime!(
         Bridge: bridge::{Module, Call, Storage, Config<T>, Event<T>},
 		Dao: dao::{Module, Call, Storage, Config, Event<T>},
 		Marketplace: marketplace::{Module, Call, Storage, Event<T>},
-		PriceOracle: price_oracle::{Module, Call, Storage, Event<T>, ValidateUnsigned},
+		PriceOracle: price_oracle::{Module, Call, Storage, Config, Event<T>, ValidateUnsigned},
 	}
 );
 
@@ -919,6 +964,86 @@ impl_runtime_apis! {
         }
     }
 
+    impl api::BridgeApi<Block, AccountId, BlockNumber, Balance, Hash, Moment> for Runtime {
+        fn open_proposals_progress() -> Vec<(ProposalId, Kind, u64, u64)> {
+            Bridge::open_proposals_progress()
+        }
+
+        fn pending_transfers() -> Vec<(ProposalId, Hash, Status, u32, u64)> {
+            Bridge::pending_transfers()
+        }
+
+        fn limit_history() -> Vec<(BlockNumber, Limits<Balance>)> {
+            Bridge::limit_history_newest_first()
+        }
+
+        fn proposals_awaiting(validator: AccountId) -> Vec<(ProposalId, Kind)> {
+            Bridge::proposals_awaiting(validator)
+        }
+
+        fn config_diff() -> ConfigDiff<AccountId, Balance> {
+            Bridge::config_diff()
+        }
+
+        fn would_exceed_daily(token_id: TokenId, account: AccountId, amount: Balance) -> bool {
+            Bridge::would_exceed_daily(token_id, &account, amount)
+        }
+
+        fn simulate_transfer(account: AccountId, to: sp_core::H160, token_id: TokenId, amount: Balance) -> SimulatedOutcome {
+            Bridge::simulate_transfer(&account, to, token_id, amount)
+        }
+
+        fn burn_batch_hints() -> Vec<(TokenId, sp_core::H160, Vec<Hash>)> {
+            Bridge::burn_batch_hints()
+        }
+
+        fn account_active_tokens(account: AccountId) -> Vec<TokenId> {
+            Bridge::account_active_tokens(account)
+        }
+
+        fn full_state_snapshot(cursor: ProposalId, max_items: u32) -> BridgeSnapshot<Hash, Balance> {
+            Bridge::full_state_snapshot(cursor, max_items)
+        }
+
+        fn proposal_message(proposal_id: ProposalId) -> Option<ProposalMessage<AccountId, Hash, Balance>> {
+            Bridge::proposal_message(proposal_id)
+        }
+
+        fn config_at(block: BlockNumber) -> Option<BridgeConfigSnapshot<Balance>> {
+            Bridge::config_at(block)
+        }
+
+        fn eth_address_volume(address: sp_core::H160) -> (Balance, Balance) {
+            Bridge::eth_address_volume(address)
+        }
+
+        fn all_token_limits() -> Vec<(TokenId, Limits<Balance>)> {
+            Bridge::all_token_limits()
+        }
+
+        fn next_daily_reset() -> Moment {
+            Bridge::next_daily_reset()
+        }
+    }
+
+    impl api::OracleApi<Block, Moment, Balance> for Runtime {
+        fn price_points(symbol: Vec<u8>) -> Vec<(Moment, Balance)> {
+            PriceOracle::price_points(symbol)
+        }
+
+        fn latest_price(symbol: Vec<u8>) -> Option<(Moment, Balance, u8)> {
+            PriceOracle::latest_price(symbol)
+        }
+
+        fn twap(symbol: Vec<u8>, window: Moment) -> Option<Balance> {
+            PriceOracle::twap(symbol, window)
+        }
+
+        fn price_warming_up(symbol: Vec<u8>) -> bool {
+            PriceOracle::is_warming_up(&symbol)
+        }
+    }
+
     impl sp_session::SessionKeys<Block> for Runtime {
         fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
             SessionKeys::generate(seed)
@@ -991,6 +1116,13 @@ impl_runtime_apis! {
                     steps,
                     repeat,
                 ),
+                b"price-oracle" | b"price_oracle" => PriceOracle::run_benchmark(
+                    extrinsic,
+                    lowest_range_values,
+                    highest_range_values,
+                    steps,
+                    repeat,
+                ),
                 b"pallet-treasury" | b"treasury" => Treasury::run_benchmark(
                     extrinsic,
                     lowest_range_values,
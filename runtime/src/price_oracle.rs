@@ -9,10 +9,14 @@
 /// and alpha release example-offchain-worker frame
 /// https://github.com/paritytech/substrate/blob/master/frame/example-offchain-worker/src/lib.rs
 ///
+use crate::types::{AggregationMethod, OracleMode};
 use codec::Encode;
 use frame_support::{ weights::SimpleDispatchInfo,
-    debug, decl_event, decl_module, decl_storage, dispatch, traits::Get, IterableStorageMap,
+    debug, decl_event, decl_module, decl_storage, dispatch, ensure, traits::{EnsureOrigin, Get}, IterableStorageMap,
+    StorageMap, StorageValue,
 };
+// floats only parse fetched JSON in the offchain worker, `round_value` needs
+// FloatCore::round without std. On-chain aggregation is integer only.
 #[cfg(not(feature = "std"))]
 #[allow(unused)]
 use num_traits::float::FloatCore;
@@ -21,13 +25,13 @@ use sp_core::crypto::KeyTypeId;
 use sp_io::{self, misc::print_utf8 as print_bytes};
 use sp_runtime::{
     offchain::http,
-    traits::{SaturatedConversion, Zero},
+    traits::{CheckedAdd, CheckedDiv, CheckedMul, SaturatedConversion, Saturating, Zero},
     transaction_validity::{InvalidTransaction, TransactionValidity, ValidTransaction},
 };
 
 // We have to import a few things
 use sp_std::prelude::*;
-use system::ensure_none;
+use system::{ensure_none, ensure_root};
 use system::offchain::SubmitUnsignedTransaction;
 
 type Result<T> = core::result::Result<T, &'static str>;
@@ -38,7 +42,34 @@ type Result<T> = core::result::Result<T, &'static str>;
 /// but only to app-specific subkeys, which are defined and grouped by their `KeyTypeId`.
 pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"ofpf");
 
-pub const TOKENS_TO_KEEP: usize = 10;
+/// Primary prices older than this (in milliseconds) are stale and the backup source is used.
+pub const PRICE_FRESHNESS_WINDOW: u32 = 10 * 60 * 1000;
+/// Decimals of the recorded USD prices.
+pub const PRICE_DECIMALS: u8 = 18;
+
+/// Weights of the oracle extrinsics: a base cost plus the cost of every stored item
+/// the call decodes and re-encodes. Calibrate with the `runtime-benchmarks` below.
+pub mod weights {
+    use frame_support::weights::Weight;
+
+    const BASE: Weight = 10_000;
+    const PER_PRICE_POINT: Weight = 500;
+    const PER_TRACKED_TOKEN: Weight = 2_000;
+
+    /// appends to price history and timestamped points
+    pub fn record_price(history_len: u32) -> Weight {
+        BASE + 2 * PER_PRICE_POINT * Weight::from(history_len)
+    }
+
+    /// trims price history and timestamped points
+    pub fn record_aggregated_price(history_len: u32) -> Weight {
+        BASE + 2 * PER_PRICE_POINT * Weight::from(history_len)
+    }
+
+    pub fn manage_tracked_tokens(tracked: u32) -> Weight {
+        BASE + PER_TRACKED_TOKEN * Weight::from(tracked)
+    }
+}
 
 // REVIEW-CHECK: is it necessary to wrap-around storage vector at `MAX_VEC_LEN`?
 // pub const MAX_VEC_LEN: usize = 1000;
@@ -49,6 +80,7 @@ pub mod crypto {
     app_crypto!(sr25519, KEY_TYPE);
 }
 
+/// Price sources the oracle starts with, changed afterwards by governance.
 pub const FETCHED_CRYPTOS: [(&[u8], &[u8], &[u8]); 4] = [
     (b"DAI", b"coincap", b"https://api.coincap.io/v2/assets/dai"),
     (
@@ -68,6 +100,13 @@ pub const FETCHED_CRYPTOS: [(&[u8], &[u8], &[u8]); 4] = [
     ),
 ];
 
+fn default_price_sources() -> Vec<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+    FETCHED_CRYPTOS
+        .iter()
+        .map(|(symbol, remote_src, remote_url)| (symbol.to_vec(), remote_src.to_vec(), remote_url.to_vec()))
+        .collect()
+}
+
 /// The module's configuration trait.
 pub trait Trait: timestamp::Trait + balances::Trait + system::Trait {
     /// The overarching event type.
@@ -85,6 +124,22 @@ pub trait Trait: timestamp::Trait + balances::Trait + system::Trait {
     // Wait period between automated fetches. Set to 0 disable this feature.
     //   Then you need to manucally kickoff pricefetch
     type BlockFetchPeriod: Get<Self::BlockNumber>;
+
+    /// Maximum number of tokens added to the oracle on top of `PriceSources`.
+    type MaxTrackedTokens: Get<u32>;
+
+    /// Origin allowed to force prices besides root.
+    type EmergencyOrigin: EnsureOrigin<Self::Origin>;
+
+    /// Percent a price point may deviate from the median of the window before it's discarded.
+    type OutlierThresholdPercent: Get<u32>;
+
+    /// Price points of a token kept after each aggregation.
+    /// History is trimmed every block, at most one fetch lands in between.
+    type PriceHistoryDepth: Get<u32>;
+
+    /// Price points recorded for a token before its aggregate is trusted.
+    type PriceWarmupPoints: Get<u32>;
 }
 
 decl_event!(
@@ -95,6 +150,10 @@ decl_event!(
     {
         FetchedPrice(Vec<u8>, Vec<u8>, Moment, Balance),
         AggregatedPrice(Vec<u8>, Moment, Balance),
+        // aggregate not published, fewer than two points are close to the median
+        PriceRejected(Vec<u8>, Balance),
+        // aggregate not published, the newest price point was recorded at the moment
+        StalePrice(Vec<u8>, Moment),
     }
 );
 
@@ -108,9 +167,55 @@ decl_storage! {
     pub TokenPriceHistory get(fn token_price_history):
     map hasher(blake2_128_concat) Vec<u8> => Vec<T::Balance>;
 
+    // same window as TokenPriceHistory with the time each price was recorded
+    pub TokenPricePoints get(fn token_price_points):
+    map hasher(blake2_128_concat) Vec<u8> => Vec<(T::Moment, T::Balance)>;
+
     // storage about aggregated price points (calculated with our logic)
     pub AggregatedPrices get(fn aggregated_prices):
     map hasher(blake2_128_concat) Vec<u8> => (T::Moment, T::Balance);
+
+    // price points ever recorded from the primary source, the token warms up until PriceWarmupPoints
+    pub ObservedPoints get(fn observed_points):
+    map hasher(blake2_128_concat) Vec<u8> => u32;
+
+    // block of the last aggregate recorded for the token, an old one means the price is stale
+    pub LastAggregatedBlock get(fn last_aggregated_block):
+    map hasher(blake2_128_concat) Vec<u8> => T::BlockNumber;
+
+    // keep the last aggregate instead of republishing old prices when the token has no fresh ones
+    pub KeepLastAggregate get(fn keep_last_aggregate): bool = true;
+
+    // oldest a token's newest price point may be for its aggregate to be published, 0 disables
+    pub MaxPriceAge get(fn max_price_age): T::Moment;
+
+    // Live aggregates fetched prices, Fixed uses FixedPrices set by governance
+    pub Mode get(fn oracle_mode): OracleMode;
+    // how live prices of a token are combined into the aggregate
+    pub Method get(fn aggregation_method): AggregationMethod;
+    pub FixedPrices get(fn fixed_price):
+    map hasher(blake2_128_concat) Vec<u8> => Option<T::Balance>;
+
+    // mapping of token symbol -> backup remote source, consulted when the primary goes stale
+    pub BackupSource get(fn backup_source):
+    map hasher(blake2_128_concat) Vec<u8> => Vec<u8>;
+
+    // (timestamp, price) points recorded from the backup source, same window as TokenPricePoints
+    pub BackupPricePoints get(fn backup_price_points):
+    map hasher(blake2_128_concat) Vec<u8> => Vec<(T::Moment, T::Balance)>;
+
+    // mapping of token symbol -> (remote_src, remote_url) fetched every BlockFetchPeriod
+    pub PriceSources get(fn price_source) build(|config: &GenesisConfig| {
+      config.price_sources.iter()
+      .map(|(symbol, remote_src, remote_url)| (symbol.clone(), (remote_src.clone(), remote_url.clone())))
+      .collect::<Vec<_>>()
+    }): map hasher(blake2_128_concat) Vec<u8> => (Vec<u8>, Vec<u8>);
+
+    // tokens added by governance as (symbol, remote_src, remote_url)
+    pub TrackedTokens get(fn tracked_tokens): Vec<(Vec<u8>, Vec<u8>, Vec<u8>)>;
+  }
+  add_extra_genesis {
+    config(price_sources): Vec<(Vec<u8>, Vec<u8>, Vec<u8>)> = default_price_sources();
   }
 }
 
@@ -122,7 +227,7 @@ decl_module! {
     // this is needed only if you are using events in your module
     fn deposit_event() = default;
 
-    #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+    #[weight = SimpleDispatchInfo::FixedNormal(weights::record_price(T::PriceHistoryDepth::get() + 1))]
     pub fn record_price_unsigned(
         origin,
         _block_number: T::BlockNumber,
@@ -141,7 +246,13 @@ decl_module! {
     //     price
     // );
 
-    <TokenPriceHistory<T>>::mutate(&symbol, |prices| prices.push(price));
+    if <BackupSource>::contains_key(&symbol) && Self::backup_source(&symbol) == remote_src {
+        <BackupPricePoints<T>>::mutate(&symbol, |points| points.push((now, price)));
+    } else {
+        <TokenPriceHistory<T>>::mutate(&symbol, |prices| prices.push(price));
+        <TokenPricePoints<T>>::mutate(&symbol, |points| points.push((now, price)));
+        <ObservedPoints>::mutate(&symbol, |count| *count = count.saturating_add(1));
+    }
 
       // Spit out an event and Add to storage
       Self::deposit_event(RawEvent::FetchedPrice(symbol, remote_src, now, price));
@@ -149,10 +260,10 @@ decl_module! {
       Ok(())
     }
 
-    #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+    #[weight = SimpleDispatchInfo::FixedNormal(weights::record_aggregated_price(T::PriceHistoryDepth::get() + 1))]
     pub fn record_aggregated_price_points_unsigned(
       origin,
-      _block: T::BlockNumber,
+      block: T::BlockNumber,
       symbol: Vec<u8>,
       price: T::Balance
     ) -> dispatch::DispatchResult {
@@ -164,19 +275,41 @@ decl_module! {
     ensure_none(origin)?;
 
     let now = <timestamp::Module<T>>::get();
+    let price = match Self::oracle_mode() {
+        OracleMode::Live => {
+            let max_age = Self::max_price_age();
+            if !max_age.is_zero() && !Self::price_is_fresh(&symbol, max_age) {
+                let newest = Self::newest_price_moment(&symbol).unwrap_or_default();
+                Self::deposit_event(RawEvent::StalePrice(symbol, newest));
+                return Ok(());
+            }
+            let window = Self::live_prices(&symbol);
+            if window.len() > 1 && Self::without_outliers(&window)?.len() < 2 {
+                Self::deposit_event(RawEvent::PriceRejected(symbol, price));
+                return Ok(());
+            }
+            price
+        }
+        OracleMode::Fixed => Self::fixed_price(&symbol).ok_or("No fixed price set for the token")?,
+    };
 
     let price_pt = (now.clone(), price.clone());
     <AggregatedPrices<T>>::insert(&symbol, price_pt);
+    <LastAggregatedBlock<T>>::insert(&symbol, block);
 
 
-    let mut old_vec = <TokenPriceHistory<T>>::get(&symbol);
-    let new_vec =  if old_vec.len() < TOKENS_TO_KEEP {
-        old_vec
-    }else{
-        let preserve_from_index = &old_vec.len().checked_sub(TOKENS_TO_KEEP).unwrap_or(9usize);
-        old_vec.drain(preserve_from_index..).collect::<Vec<T::Balance>>()
-    };
-    <TokenPriceHistory<T>>::insert(&symbol, new_vec);
+    let depth = T::PriceHistoryDepth::get() as usize;
+    let mut history = <TokenPriceHistory<T>>::get(&symbol);
+    let preserve_from_index = history.len().saturating_sub(depth);
+    <TokenPriceHistory<T>>::insert(&symbol, history.split_off(preserve_from_index));
+
+    let mut points = <TokenPricePoints<T>>::get(&symbol);
+    let preserve_from_index = points.len().saturating_sub(depth);
+    <TokenPricePoints<T>>::insert(&symbol, points.split_off(preserve_from_index));
+
+    let mut backup_points = <BackupPricePoints<T>>::get(&symbol);
+    let preserve_from_index = backup_points.len().saturating_sub(depth);
+    <BackupPricePoints<T>>::insert(&symbol, backup_points.split_off(preserve_from_index));
 
       Self::deposit_event(RawEvent::AggregatedPrice(
         symbol.clone(), now.clone(), price.clone()));
@@ -184,18 +317,120 @@ decl_module! {
       Ok(())
     }
 
+    #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+    pub fn set_oracle_mode(origin, mode: OracleMode) -> dispatch::DispatchResult {
+      ensure_root(origin)?;
+      <Mode>::put(mode);
+      Ok(())
+    }
+
+    #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+    pub fn set_aggregation_method(origin, method: AggregationMethod) -> dispatch::DispatchResult {
+      ensure_root(origin)?;
+      <Method>::put(method);
+      Ok(())
+    }
+
+    #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+    pub fn set_max_price_age(origin, max_age: T::Moment) -> dispatch::DispatchResult {
+      ensure_root(origin)?;
+      <MaxPriceAge<T>>::put(max_age);
+      Ok(())
+    }
+
+    #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+    pub fn set_keep_last_aggregate(origin, enabled: bool) -> dispatch::DispatchResult {
+      ensure_root(origin)?;
+      <KeepLastAggregate>::put(enabled);
+      Ok(())
+    }
+
+    #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+    pub fn force_set_price(origin, symbol: Vec<u8>, price: T::Balance) -> dispatch::DispatchResult {
+      T::EmergencyOrigin::ensure_origin(origin)?;
+      <FixedPrices<T>>::insert(&symbol, price);
+      Ok(())
+    }
+
+    #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+    pub fn set_backup_source(origin, symbol: Vec<u8>, remote_src: Vec<u8>) -> dispatch::DispatchResult {
+      ensure_root(origin)?;
+      ensure!(Self::is_supported_source(&remote_src), "Unknown remote source");
+      ensure!(Self::is_tracked(&symbol), "Token is not tracked");
+      <BackupSource>::insert(&symbol, remote_src);
+      Ok(())
+    }
+
+    #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+    pub fn remove_backup_source(origin, symbol: Vec<u8>) -> dispatch::DispatchResult {
+      ensure_root(origin)?;
+      <BackupSource>::remove(&symbol);
+      <BackupPricePoints<T>>::remove(&symbol);
+      Ok(())
+    }
+
+    // governance adds a price source or replaces the endpoint of an existing one
+    #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+    pub fn add_price_source(
+      origin,
+      symbol: Vec<u8>,
+      remote_src: Vec<u8>,
+      remote_url: Vec<u8>
+    ) -> dispatch::DispatchResult {
+      ensure_root(origin)?;
+      ensure!(Self::is_supported_source(&remote_src), "Unknown remote source");
+      ensure!(!Self::tracked_tokens().iter().any(|(s, ..)| *s == symbol), "Token is already tracked");
+      <PriceSources>::insert(&symbol, (remote_src, remote_url));
+      Ok(())
+    }
+
+    #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+    pub fn remove_price_source(origin, symbol: Vec<u8>) -> dispatch::DispatchResult {
+      ensure_root(origin)?;
+      ensure!(<PriceSources>::contains_key(&symbol), "Unknown price source");
+      <PriceSources>::remove(&symbol);
+      <ObservedPoints>::remove(&symbol);
+      Ok(())
+    }
+
+    #[weight = SimpleDispatchInfo::FixedNormal(weights::manage_tracked_tokens(T::MaxTrackedTokens::get()))]
+    pub fn add_tracked_token(
+      origin,
+      symbol: Vec<u8>,
+      remote_src: Vec<u8>,
+      remote_url: Vec<u8>
+    ) -> dispatch::DispatchResult {
+      ensure_root(origin)?;
+      ensure!(Self::is_supported_source(&remote_src), "Unknown remote source");
+      ensure!(!Self::is_tracked(&symbol), "Token is already tracked");
+
+      let mut tracked = Self::tracked_tokens();
+      ensure!((tracked.len() as u32) < T::MaxTrackedTokens::get(), "Too many tracked tokens");
+      tracked.push((symbol, remote_src, remote_url));
+      <TrackedTokens>::put(tracked);
+      Ok(())
+    }
+
+    #[weight = SimpleDispatchInfo::FixedNormal(weights::manage_tracked_tokens(T::MaxTrackedTokens::get()))]
+    pub fn remove_tracked_token(origin, symbol: Vec<u8>) -> dispatch::DispatchResult {
+      ensure_root(origin)?;
+      <TrackedTokens>::mutate(|tracked| tracked.retain(|(s, ..)| *s != symbol));
+      <ObservedPoints>::remove(&symbol);
+      Ok(())
+    }
+
     fn offchain_worker(block: T::BlockNumber) {
       let duration = T::BlockFetchPeriod::get();
 
       // Type I task: fetch price
       if duration > 0.into() && block % duration == 0.into() {
-        for (symbol, remote_src, remote_url) in FETCHED_CRYPTOS.iter() {
-          let res = Self::fetch_price_unsigned(block, *symbol, *remote_src, *remote_url);
+        for (symbol, remote_src, remote_url) in Self::fetched_sources().iter() {
+          let res = Self::fetch_price_unsigned(block, symbol, remote_src, remote_url);
 
           if let Err(e) = res {
             debug::error!("Error fetching: {:?}, {:?}: {:?}",
-            core::str::from_utf8(symbol).unwrap(),
-            core::str::from_utf8(remote_src).unwrap(),
+            core::str::from_utf8(symbol).unwrap_or_default(),
+            core::str::from_utf8(remote_src).unwrap_or_default(),
             e);
           }
         }
@@ -204,13 +439,13 @@ decl_module! {
       // Type II task: aggregate price
       <TokenPriceHistory<T>>::iter()
       // filter those to be updated
-      .filter(|(_, vec)| vec.len() > 0)
+      .filter(|(symbol, vec)| vec.len() > 0 || !<BackupPricePoints<T>>::get(symbol).is_empty())
       .for_each(|(symbol, _)| {
         let res = Self::aggregate_price_points_unsigned(block, &symbol);
 
         if let Err(e) = res {
           debug::error!("Error aggregating price of {:?}: {:?}",
-          core::str::from_utf8(&symbol).unwrap(), e);
+          core::str::from_utf8(&symbol).unwrap_or_default(), e);
         }
         });
     }
@@ -219,6 +454,61 @@ decl_module! {
 }
 
 impl<T: Trait> Module<T> {
+    /// retained (timestamp, price) points of the token, oldest first
+    pub fn price_points(symbol: Vec<u8>) -> Vec<(T::Moment, T::Balance)> {
+        Self::token_price_points(symbol)
+    }
+
+    /// (timestamp, price, decimals of the price) of the token's aggregate
+    pub fn latest_price(symbol: Vec<u8>) -> Option<(T::Moment, T::Balance, u8)> {
+        if !<AggregatedPrices<T>>::contains_key(&symbol) {
+            return None;
+        }
+        let (recorded_at, price) = Self::aggregated_prices(symbol);
+        Some((recorded_at, price, PRICE_DECIMALS))
+    }
+
+    /// (symbol, remote_src, remote_url) of every token the offchain worker fetches
+    pub fn fetched_sources() -> Vec<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+        <PriceSources>::iter()
+            .map(|(symbol, (remote_src, remote_url))| (symbol, remote_src, remote_url))
+            .chain(Self::tracked_tokens())
+            .collect()
+    }
+
+    /// time weighted average of the token's price over the last `window`,
+    /// each point holds until the next one is recorded
+    pub fn twap(symbol: Vec<u8>, window: T::Moment) -> Option<T::Balance> {
+        let now = <timestamp::Module<T>>::get();
+        let start = now.saturating_sub(window);
+        let points = Self::token_price_points(symbol);
+        // the price in force at the start of the window was recorded before it
+        let first = points.iter().rposition(|(at, _)| *at <= start).unwrap_or(0);
+
+        let mut weighted = T::Balance::zero();
+        let mut elapsed = T::Balance::zero();
+        for (i, (at, price)) in points.iter().enumerate().skip(first) {
+            let from = (*at).max(start);
+            let until = points.get(i + 1).map_or(now, |(next, _)| *next);
+            let duration: T::Balance = until.saturating_sub(from).saturated_into::<u128>().saturated_into();
+            weighted = weighted.checked_add(&price.checked_mul(&duration)?)?;
+            elapsed = elapsed.checked_add(&duration)?;
+        }
+        if elapsed.is_zero() {
+            return points.last().map(|(_, price)| *price);
+        }
+        weighted.checked_div(&elapsed)
+    }
+
+    fn is_supported_source(remote_src: &[u8]) -> bool {
+        [&b"coingecko"[..], b"coincap", b"cryptocompare"].contains(&remote_src)
+    }
+
+    fn is_tracked(symbol: &[u8]) -> bool {
+        <PriceSources>::contains_key(symbol)
+            || Self::tracked_tokens().iter().any(|(s, ..)| s.as_slice() == symbol)
+    }
+
     fn fetch_json<'a>(remote_url: &'a [u8]) -> Result<JsonValue> {
         //TODO: add deadline for request
         let remote_url_str = core::str::from_utf8(remote_url)
@@ -293,7 +583,7 @@ impl<T: Trait> Module<T> {
 
     fn round_value(v: f64) -> T::Balance {
         let mut precisioned: u128 = (v * 1000000000.0).round() as u128;
-        precisioned = precisioned * 1000000000; // saturate to 10^18 precision
+        precisioned = precisioned * 1000000000; // saturate to 10^PRICE_DECIMALS precision
         let balance = precisioned.saturated_into::<T::Balance>();
         balance
     }
@@ -338,15 +628,136 @@ impl<T: Trait> Module<T> {
         Ok(Self::round_value(val_f64))
     }
 
-    fn aggregate_price_points_unsigned<'a>(block: T::BlockNumber, symbol: &'a [u8]) -> Result<()> {
-        let token_pricepoints_vec = <TokenPriceHistory<T>>::get(symbol);
-        let price_sum: T::Balance = token_pricepoints_vec
-            .iter()
-            .fold(T::Balance::zero(), |mem, price| mem + *price);
+    /// average of the price points, errors instead of panicking on
+    /// empty history or overflow since it runs on every block
+    fn average_price(prices: &[T::Balance]) -> Result<T::Balance> {
+        let price_sum = prices.iter().try_fold(T::Balance::zero(), |mem, price| {
+            mem.checked_add(price).ok_or("Overflow summing price points")
+        })?;
 
         // Avoiding floating-point arithmetic & do integer division
-        let price_avg: T::Balance =
-            price_sum / T::Balance::from(token_pricepoints_vec.len() as u32);
+        price_sum
+            .checked_div(&T::Balance::from(prices.len() as u32))
+            .ok_or("No price points to aggregate")
+    }
+
+    /// middle of the sorted price points, mean of the two middle ones for even length
+    fn median_price(prices: &[T::Balance]) -> Result<T::Balance> {
+        let mut sorted = prices.to_vec();
+        sorted.sort();
+        let mid = sorted.len() / 2;
+        match sorted.len() {
+            0 => Err("No price points to aggregate"),
+            len if len % 2 == 1 => Ok(sorted[mid]),
+            _ => Self::average_price(&sorted[mid - 1..=mid]),
+        }
+    }
+
+    /// price points within `OutlierThresholdPercent` of the median of the window
+    fn without_outliers(prices: &[T::Balance]) -> Result<Vec<T::Balance>> {
+        let median = Self::median_price(prices)?;
+        let threshold = T::Balance::from(T::OutlierThresholdPercent::get());
+        let max_deviation = median.saturating_mul(threshold);
+        Ok(prices
+            .iter()
+            .cloned()
+            .filter(|p| {
+                let deviation = if *p > median { *p - median } else { median - *p };
+                deviation.saturating_mul(T::Balance::from(100)) <= max_deviation
+            })
+            .collect())
+    }
+
+    /// whether the newest price point of the token is at most `max_age` old
+    pub fn price_is_fresh(symbol: &[u8], max_age: T::Moment) -> bool {
+        let now = <timestamp::Module<T>>::get();
+        Self::newest_price_moment(symbol)
+            .map_or(false, |recorded_at| recorded_at.saturating_add(max_age) >= now)
+    }
+
+    /// whether too few prices of the token were recorded to trust its aggregate
+    pub fn is_warming_up(symbol: &[u8]) -> bool {
+        <ObservedPoints>::get(symbol) < T::PriceWarmupPoints::get()
+    }
+
+    /// whether the token has an aggregate recorded within `max_age`
+    pub fn has_fresh_aggregate(symbol: &[u8], max_age: T::Moment) -> bool {
+        if !<AggregatedPrices<T>>::contains_key(symbol) {
+            return false;
+        }
+        let (recorded_at, _) = Self::aggregated_prices(symbol);
+        recorded_at.saturating_add(max_age) >= <timestamp::Module<T>>::get()
+    }
+
+    /// when the newest primary or backup price point of the token was recorded
+    fn newest_price_moment(symbol: &[u8]) -> Option<T::Moment> {
+        let primary = <TokenPricePoints<T>>::get(symbol).last().map(|(at, _)| *at);
+        let backup = <BackupPricePoints<T>>::get(symbol).last().map(|(at, _)| *at);
+        primary.max(backup)
+    }
+
+    /// whether a primary or backup price point of the token was recorded within
+    /// `MaxPriceAge`, or `PRICE_FRESHNESS_WINDOW` while it isn't set
+    fn has_fresh_points(symbol: &[u8]) -> bool {
+        let max_age = Self::max_price_age();
+        if max_age.is_zero() {
+            Self::price_is_fresh(symbol, T::Moment::from(PRICE_FRESHNESS_WINDOW))
+        } else {
+            Self::price_is_fresh(symbol, max_age)
+        }
+    }
+
+    /// whether the point was recorded within `PRICE_FRESHNESS_WINDOW`
+    fn is_fresh(recorded_at: T::Moment, now: T::Moment) -> bool {
+        recorded_at.saturating_add(T::Moment::from(PRICE_FRESHNESS_WINDOW)) >= now
+    }
+
+    /// prices to aggregate in live mode: primary ones while fresh,
+    /// otherwise fresh points of the backup source if there are any
+    fn live_prices(symbol: &[u8]) -> Vec<T::Balance> {
+        let now = <timestamp::Module<T>>::get();
+        let primary_fresh = <TokenPricePoints<T>>::get(symbol)
+            .last()
+            .map_or(false, |(recorded_at, _)| Self::is_fresh(*recorded_at, now));
+        if !primary_fresh && <BackupSource>::contains_key(symbol) {
+            let backup = <BackupPricePoints<T>>::get(symbol)
+                .into_iter()
+                .filter(|(recorded_at, _)| Self::is_fresh(*recorded_at, now))
+                .map(|(_, price)| price)
+                .collect::<Vec<_>>();
+            if !backup.is_empty() {
+                return backup;
+            }
+        }
+        <TokenPriceHistory<T>>::get(symbol)
+    }
+
+    /// price to aggregate for the token depending on the oracle mode
+    fn aggregated_price(symbol: &[u8]) -> Result<T::Balance> {
+        match Self::oracle_mode() {
+            OracleMode::Live => {
+                let window = Self::live_prices(symbol);
+                // too few points agree: aggregate the whole window, the chain rejects it
+                let survivors = Self::without_outliers(&window).unwrap_or_default();
+                let prices = if survivors.len() < 2 { window } else { survivors };
+                match Self::aggregation_method() {
+                    AggregationMethod::Mean => Self::average_price(&prices),
+                    AggregationMethod::Median => Self::median_price(&prices),
+                }
+            }
+            OracleMode::Fixed => Self::fixed_price(symbol).ok_or("No fixed price set for the token"),
+        }
+    }
+
+    fn aggregate_price_points_unsigned<'a>(block: T::BlockNumber, symbol: &'a [u8]) -> Result<()> {
+        // sources are down: the last aggregate stays, LastAggregatedBlock shows its age
+        let outage = Self::oracle_mode() == OracleMode::Live && !Self::has_fresh_points(symbol);
+        if outage && Self::keep_last_aggregate() && <AggregatedPrices<T>>::contains_key(symbol) {
+            debug::warn!("No fresh prices to aggregate for {:?}, keeping the last aggregate",
+            core::str::from_utf8(symbol).unwrap_or_default());
+            return Ok(());
+        }
+        let price_avg = Self::aggregated_price(symbol)?;
 
         let call = Call::record_aggregated_price_points_unsigned(block, symbol.to_vec(), price_avg);
 
@@ -403,6 +814,51 @@ impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
     }
 }
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking {
+    use super::*;
+    use frame_benchmarking::benchmarks;
+    use system::RawOrigin;
+
+    fn fill_history<T: Trait>(symbol: &[u8], len: u32) {
+        let now = <timestamp::Module<T>>::get();
+        let price = T::Balance::from(1u32);
+        <TokenPriceHistory<T>>::insert(symbol, vec![price; len as usize]);
+        <TokenPricePoints<T>>::insert(symbol, vec![(now, price); len as usize]);
+    }
+
+    benchmarks! {
+        _ { }
+
+        record_price_unsigned {
+            let h in 0 .. T::PriceHistoryDepth::get() + 1;
+            fill_history::<T>(b"DAI", h);
+            let crypto_info = (b"DAI".to_vec(), b"coincap".to_vec(), Vec::new());
+        }: _(RawOrigin::None, T::BlockNumber::from(0), crypto_info, T::Balance::from(1u32))
+
+        record_aggregated_price_points_unsigned {
+            let h in 0 .. T::PriceHistoryDepth::get() + 1;
+            fill_history::<T>(b"DAI", h);
+        }: _(RawOrigin::None, T::BlockNumber::from(0), b"DAI".to_vec(), T::Balance::from(1u32))
+
+        add_tracked_token {
+            let t in 0 .. T::MaxTrackedTokens::get() - 1;
+            let tracked = (0..t)
+                .map(|i| (i.encode(), b"coincap".to_vec(), Vec::new()))
+                .collect::<Vec<_>>();
+            <TrackedTokens>::put(tracked);
+        }: _(RawOrigin::Root, b"NEW".to_vec(), b"coincap".to_vec(), Vec::new())
+
+        remove_tracked_token {
+            let t in 1 .. T::MaxTrackedTokens::get();
+            let tracked = (0..t)
+                .map(|i| (i.encode(), b"coincap".to_vec(), Vec::new()))
+                .collect::<Vec<_>>();
+            <TrackedTokens>::put(tracked);
+        }: _(RawOrigin::Root, 0u32.encode())
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     /// tests for this module
@@ -412,12 +868,18 @@ pub mod tests {
     //  3. with multiple record_price of same symbol inserted. On next cycle, the average of the price is calculated
     //  4. can fetch for BTC, parse the JSON blob and get a price > 0 out
     use super::*;
-    use frame_support::{impl_outer_dispatch, impl_outer_origin, parameter_types, weights::Weight};
-    use sp_core::H256;
+    use frame_support::{
+        assert_noop, assert_ok, impl_outer_dispatch, impl_outer_origin, parameter_types,
+        weights::Weight,
+    };
+    use sp_core::{
+        offchain::{testing::TestTransactionPoolExt, TransactionPoolExt},
+        H256,
+    };
     use sp_runtime::{
         testing::{Header, TestXt},
-        traits::{BlakeTwo256, IdentityLookup},
-        Perbill,
+        traits::{BlakeTwo256, IdentityLookup, OffchainWorker},
+        DispatchError, Perbill,
     };
     use std::cell::RefCell;
 
@@ -502,6 +964,10 @@ pub mod tests {
     parameter_types! {
         pub const BlockFetchPeriod: BlockNumber = 2;
         pub const GracePeriod: BlockNumber = 5;
+        pub const MaxTrackedTokens: u32 = 2;
+        pub const OutlierThresholdPercent: u32 = 50;
+        pub const PriceHistoryDepth: u32 = 3;
+        pub const PriceWarmupPoints: u32 = 3;
     }
 
     impl Trait for Test {
@@ -513,15 +979,23 @@ pub mod tests {
         //   Then you need to manucally kickoff pricefetch
         type GracePeriod = GracePeriod;
         type BlockFetchPeriod = BlockFetchPeriod;
+        type MaxTrackedTokens = MaxTrackedTokens;
+        type EmergencyOrigin = system::EnsureRoot<u64>;
+        type OutlierThresholdPercent = OutlierThresholdPercent;
+        type PriceHistoryDepth = PriceHistoryDepth;
+        type PriceWarmupPoints = PriceWarmupPoints;
     }
 
     // This function basically just builds a genesis storage key/value store according to
     // our desired mockup.
     pub fn new_test_ext() -> sp_io::TestExternalities {
-        system::GenesisConfig::default()
+        let mut storage = system::GenesisConfig::default()
             .build_storage::<Test>()
-            .unwrap()
-            .into()
+            .unwrap();
+        GenesisConfig::default()
+            .assimilate_storage(&mut storage)
+            .unwrap();
+        storage.into()
     }
 
     #[test]
@@ -530,4 +1004,467 @@ pub mod tests {
             assert_eq!(1, 1);
         });
     }
+
+    #[test]
+    fn price_points_should_be_read_in_order() {
+        new_test_ext().execute_with(|| {
+            let crypto_info = (b"DAI".to_vec(), b"coincap".to_vec(), b"url".to_vec());
+            for (now, price) in [(6, 100), (12, 101), (18, 99)].iter() {
+                timestamp::Module::<Test>::set_timestamp(*now);
+                assert_ok!(PriceOracleModule::record_price_unsigned(
+                    Origin::NONE,
+                    1,
+                    crypto_info.clone(),
+                    *price
+                ));
+            }
+
+            assert_eq!(
+                PriceOracleModule::price_points(b"DAI".to_vec()),
+                vec![(6, 100), (12, 101), (18, 99)]
+            );
+            assert!(PriceOracleModule::price_points(b"USDT".to_vec()).is_empty());
+        });
+    }
+
+    #[test]
+    fn aggregation_should_follow_oracle_mode() {
+        new_test_ext().execute_with(|| {
+            let crypto_info = (b"DAI".to_vec(), b"coincap".to_vec(), b"url".to_vec());
+            for price in [100, 200].iter() {
+                assert_ok!(PriceOracleModule::record_price_unsigned(
+                    Origin::NONE,
+                    1,
+                    crypto_info.clone(),
+                    *price
+                ));
+            }
+            assert_ok!(PriceOracleModule::force_set_price(Origin::ROOT, b"DAI".to_vec(), 1));
+
+            assert_eq!(PriceOracleModule::oracle_mode(), OracleMode::Live);
+            assert_eq!(PriceOracleModule::aggregated_price(b"DAI"), Ok(150));
+
+            assert_ok!(PriceOracleModule::set_oracle_mode(Origin::ROOT, OracleMode::Fixed));
+            assert_eq!(PriceOracleModule::aggregated_price(b"DAI"), Ok(1));
+            assert_eq!(
+                PriceOracleModule::aggregated_price(b"USDT"),
+                Err("No fixed price set for the token")
+            );
+
+            // submitted aggregate is replaced by the fixed price
+            assert_ok!(PriceOracleModule::record_aggregated_price_points_unsigned(
+                Origin::NONE,
+                1,
+                b"DAI".to_vec(),
+                150
+            ));
+            assert_eq!(PriceOracleModule::aggregated_prices(b"DAI".to_vec()).1, 1);
+        });
+    }
+
+    #[test]
+    fn aggregation_should_not_panic_on_pathological_history() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                PriceOracleModule::average_price(&[]),
+                Err("No price points to aggregate")
+            );
+            assert_eq!(PriceOracleModule::average_price(&[42]), Ok(42));
+            assert_eq!(
+                PriceOracleModule::average_price(&[u128::max_value(), 1]),
+                Err("Overflow summing price points")
+            );
+
+            // nothing recorded for the symbol yet
+            assert_eq!(
+                PriceOracleModule::aggregate_price_points_unsigned(1, b"DAI"),
+                Err("No price points to aggregate")
+            );
+        });
+    }
+
+    #[test]
+    fn add_tracked_token_should_respect_cap() {
+        new_test_ext().execute_with(|| {
+            let src = b"cryptocompare".to_vec();
+            let url = b"https://min-api.cryptocompare.com/data/price?fsym=BTC&tsyms=USD".to_vec();
+
+            assert_noop!(
+                PriceOracleModule::add_tracked_token(Origin::ROOT, b"DAI".to_vec(), src.clone(), url.clone()),
+                DispatchError::Other("Token is already tracked")
+            );
+            assert_ok!(PriceOracleModule::add_tracked_token(
+                Origin::ROOT,
+                b"BTC".to_vec(),
+                src.clone(),
+                url.clone()
+            ));
+            assert_ok!(PriceOracleModule::add_tracked_token(
+                Origin::ROOT,
+                b"ETH".to_vec(),
+                src.clone(),
+                url.clone()
+            ));
+            assert_noop!(
+                PriceOracleModule::add_tracked_token(Origin::ROOT, b"LINK".to_vec(), src.clone(), url.clone()),
+                DispatchError::Other("Too many tracked tokens")
+            );
+            assert_eq!(PriceOracleModule::tracked_tokens().len(), 2);
+
+            assert_ok!(PriceOracleModule::remove_tracked_token(Origin::ROOT, b"BTC".to_vec()));
+            assert_ok!(PriceOracleModule::add_tracked_token(Origin::ROOT, b"LINK".to_vec(), src, url));
+        });
+    }
+
+    #[test]
+    fn aggregation_should_fall_back_to_backup_source_when_primary_is_stale() {
+        new_test_ext().execute_with(|| {
+            let primary = (b"DAI".to_vec(), b"coincap".to_vec(), b"url".to_vec());
+            let backup = (b"DAI".to_vec(), b"coingecko".to_vec(), b"url".to_vec());
+            assert_noop!(
+                PriceOracleModule::set_backup_source(Origin::ROOT, b"DAI".to_vec(), b"unknown".to_vec()),
+                DispatchError::Other("Unknown remote source")
+            );
+            assert_ok!(PriceOracleModule::set_backup_source(
+                Origin::ROOT,
+                b"DAI".to_vec(),
+                b"coingecko".to_vec()
+            ));
+
+            timestamp::Module::<Test>::set_timestamp(1_000);
+            assert_ok!(PriceOracleModule::record_price_unsigned(Origin::NONE, 1, primary, 100));
+            assert_ok!(PriceOracleModule::record_price_unsigned(Origin::NONE, 1, backup.clone(), 300));
+            // primary is fresh and preferred
+            assert_eq!(PriceOracleModule::aggregated_price(b"DAI"), Ok(100));
+
+            let later = 1_000 + u64::from(PRICE_FRESHNESS_WINDOW) + 1;
+            timestamp::Module::<Test>::set_timestamp(later);
+            assert_ok!(PriceOracleModule::record_price_unsigned(Origin::NONE, 2, backup.clone(), 200));
+            assert_ok!(PriceOracleModule::record_price_unsigned(Origin::NONE, 2, backup, 210));
+
+            // only fresh backup points are used
+            assert_eq!(PriceOracleModule::aggregated_price(b"DAI"), Ok(205));
+            assert_eq!(PriceOracleModule::token_price_history(b"DAI".to_vec()), vec![100]);
+
+            // without a backup the stale primary history is kept
+            assert_ok!(PriceOracleModule::remove_backup_source(Origin::ROOT, b"DAI".to_vec()));
+            assert_eq!(PriceOracleModule::aggregated_price(b"DAI"), Ok(100));
+        });
+    }
+
+    #[test]
+    fn aggregation_should_match_integer_average() {
+        new_test_ext().execute_with(|| {
+            let samples: Vec<Vec<u128>> = vec![
+                vec![1],
+                vec![1, 2],
+                vec![2, 3, 3],
+                vec![999_999_999_999_999_999, 1_000_000_000_000_000_001, 7],
+                (1..=10u128).map(|p| p * 1_000_000_000_123_456_789).collect(),
+                vec![u128::max_value() / 2, u128::max_value() / 2],
+            ];
+            for prices in samples.iter() {
+                let expected = prices.iter().sum::<u128>() / prices.len() as u128;
+                assert_eq!(PriceOracleModule::average_price(prices), Ok(expected));
+            }
+            // truncated, never rounded up
+            assert_eq!(PriceOracleModule::average_price(&[1, 2]), Ok(1));
+            assert_eq!(PriceOracleModule::average_price(&[2, 3, 3]), Ok(2));
+        });
+    }
+
+    #[test]
+    fn offchain_worker_should_keep_last_aggregate_without_fresh_prices() {
+        let (pool, pool_state) = TestTransactionPoolExt::new();
+        let mut ext = new_test_ext();
+        ext.register_extension(TransactionPoolExt::new(pool));
+        ext.execute_with(|| {
+            let crypto_info = (b"DAI".to_vec(), b"coincap".to_vec(), b"url".to_vec());
+            timestamp::Module::<Test>::set_timestamp(6);
+            for price in [150, 151, 149].iter() {
+                assert_ok!(PriceOracleModule::record_price_unsigned(
+                    Origin::NONE,
+                    1,
+                    crypto_info.clone(),
+                    *price
+                ));
+            }
+            // odd blocks only aggregate, nothing is fetched
+            <PriceOracleModule as OffchainWorker<BlockNumber>>::offchain_worker(1);
+            assert_eq!(pool_state.read().transactions.len(), 1);
+            assert_ok!(PriceOracleModule::record_aggregated_price_points_unsigned(
+                Origin::NONE,
+                1,
+                b"DAI".to_vec(),
+                150
+            ));
+            assert_eq!(PriceOracleModule::last_aggregated_block(b"DAI".to_vec()), 1);
+
+            // sources went silent, the history still holds the old window
+            timestamp::Module::<Test>::set_timestamp(6 + u64::from(PRICE_FRESHNESS_WINDOW) + 1);
+            assert!(!PriceOracleModule::token_price_history(b"DAI".to_vec()).is_empty());
+            <PriceOracleModule as OffchainWorker<BlockNumber>>::offchain_worker(3);
+            assert_eq!(pool_state.read().transactions.len(), 1);
+            assert_eq!(PriceOracleModule::aggregated_prices(b"DAI".to_vec()), (6, 150));
+
+            assert_noop!(
+                PriceOracleModule::set_keep_last_aggregate(Origin::NONE, false),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(PriceOracleModule::set_keep_last_aggregate(Origin::ROOT, false));
+            <PriceOracleModule as OffchainWorker<BlockNumber>>::offchain_worker(5);
+            assert_eq!(pool_state.read().transactions.len(), 2);
+        });
+    }
+
+    #[test]
+    fn median_aggregation_should_ignore_outliers() {
+        new_test_ext().execute_with(|| {
+            let crypto_info = (b"DAI".to_vec(), b"coincap".to_vec(), b"url".to_vec());
+            for price in [10, 1000, 12].iter() {
+                assert_ok!(PriceOracleModule::record_price_unsigned(
+                    Origin::NONE,
+                    1,
+                    crypto_info.clone(),
+                    *price
+                ));
+            }
+
+            assert_eq!(PriceOracleModule::aggregation_method(), AggregationMethod::Mean);
+            assert_eq!(PriceOracleModule::average_price(&[10, 1000, 12]), Ok(340));
+
+            assert_ok!(PriceOracleModule::set_aggregation_method(
+                Origin::ROOT,
+                AggregationMethod::Median
+            ));
+            assert_eq!(PriceOracleModule::aggregated_price(b"DAI"), Ok(12));
+
+            assert_eq!(PriceOracleModule::median_price(&[10, 12, 1000, 14]), Ok(13));
+            assert_eq!(
+                PriceOracleModule::median_price(&[]),
+                Err("No price points to aggregate")
+            );
+        });
+    }
+
+    #[test]
+    fn aggregation_should_discard_outliers() {
+        new_test_ext().execute_with(|| {
+            let dai = (b"DAI".to_vec(), b"coincap".to_vec(), b"url".to_vec());
+            for price in [10, 1000, 12].iter() {
+                assert_ok!(PriceOracleModule::record_price_unsigned(
+                    Origin::NONE,
+                    1,
+                    dai.clone(),
+                    *price
+                ));
+            }
+            assert_eq!(PriceOracleModule::without_outliers(&[10, 1000, 12]), Ok(vec![10, 12]));
+            assert_eq!(PriceOracleModule::aggregated_price(b"DAI"), Ok(11));
+            assert_ok!(PriceOracleModule::record_aggregated_price_points_unsigned(
+                Origin::NONE,
+                1,
+                b"DAI".to_vec(),
+                11
+            ));
+            assert_eq!(PriceOracleModule::aggregated_prices(b"DAI".to_vec()).1, 11);
+
+            // no two points agree, the previous aggregate stays
+            let usdt = (b"USDT".to_vec(), b"coincap".to_vec(), b"url".to_vec());
+            assert_ok!(PriceOracleModule::record_price_unsigned(Origin::NONE, 1, usdt.clone(), 10));
+            assert_ok!(PriceOracleModule::record_aggregated_price_points_unsigned(
+                Origin::NONE,
+                1,
+                b"USDT".to_vec(),
+                10
+            ));
+            assert_ok!(PriceOracleModule::record_price_unsigned(Origin::NONE, 2, usdt, 1000));
+            assert_eq!(PriceOracleModule::aggregated_price(b"USDT"), Ok(505));
+            assert_ok!(PriceOracleModule::record_aggregated_price_points_unsigned(
+                Origin::NONE,
+                2,
+                b"USDT".to_vec(),
+                505
+            ));
+            assert_eq!(PriceOracleModule::aggregated_prices(b"USDT".to_vec()).1, 10);
+            assert_eq!(PriceOracleModule::last_aggregated_block(b"USDT".to_vec()), 1);
+        });
+    }
+
+    #[test]
+    fn stale_prices_should_not_be_republished() {
+        new_test_ext().execute_with(|| {
+            let crypto_info = (b"DAI".to_vec(), b"coincap".to_vec(), b"url".to_vec());
+            assert_ok!(PriceOracleModule::set_max_price_age(Origin::ROOT, 1_000));
+
+            timestamp::Module::<Test>::set_timestamp(100);
+            assert_ok!(PriceOracleModule::record_price_unsigned(Origin::NONE, 1, crypto_info, 100));
+            assert!(PriceOracleModule::price_is_fresh(b"DAI", 1_000));
+            assert_ok!(PriceOracleModule::record_aggregated_price_points_unsigned(
+                Origin::NONE,
+                1,
+                b"DAI".to_vec(),
+                100
+            ));
+            assert_eq!(PriceOracleModule::aggregated_prices(b"DAI".to_vec()), (100, 100));
+
+            // no new prices for far longer than the max age
+            timestamp::Module::<Test>::set_timestamp(100_000);
+            assert!(!PriceOracleModule::price_is_fresh(b"DAI", 1_000));
+            assert_ok!(PriceOracleModule::record_aggregated_price_points_unsigned(
+                Origin::NONE,
+                2,
+                b"DAI".to_vec(),
+                100
+            ));
+            assert_eq!(PriceOracleModule::aggregated_prices(b"DAI".to_vec()), (100, 100));
+            assert_eq!(PriceOracleModule::last_aggregated_block(b"DAI".to_vec()), 1);
+        });
+    }
+
+    #[test]
+    fn latest_price_should_report_decimals() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(PriceOracleModule::latest_price(b"DAI".to_vec()), None);
+
+            timestamp::Module::<Test>::set_timestamp(100);
+            assert_ok!(PriceOracleModule::record_aggregated_price_points_unsigned(
+                Origin::NONE,
+                1,
+                b"DAI".to_vec(),
+                150
+            ));
+            assert_eq!(
+                PriceOracleModule::latest_price(b"DAI".to_vec()),
+                Some((100, 150, PRICE_DECIMALS))
+            );
+            assert_eq!(PriceOracleModule::round_value(1.5), 1_500_000_000_000_000_000);
+            assert_eq!(PriceOracleModule::round_value(1.0), 10u128.pow(u32::from(PRICE_DECIMALS)));
+        });
+    }
+
+    #[test]
+    fn aggregation_should_keep_configured_history_depth() {
+        new_test_ext().execute_with(|| {
+            let crypto_info = (b"DAI".to_vec(), b"coincap".to_vec(), b"url".to_vec());
+            for (now, price) in [(6, 100), (12, 101), (18, 102), (24, 103), (30, 104)].iter() {
+                timestamp::Module::<Test>::set_timestamp(*now);
+                assert_ok!(PriceOracleModule::record_price_unsigned(
+                    Origin::NONE,
+                    1,
+                    crypto_info.clone(),
+                    *price
+                ));
+            }
+            assert_eq!(PriceOracleModule::token_price_history(b"DAI".to_vec()).len(), 5);
+
+            assert_ok!(PriceOracleModule::record_aggregated_price_points_unsigned(
+                Origin::NONE,
+                1,
+                b"DAI".to_vec(),
+                102
+            ));
+            assert_eq!(
+                PriceOracleModule::token_price_history(b"DAI".to_vec()),
+                vec![102, 103, 104]
+            );
+            assert_eq!(
+                PriceOracleModule::price_points(b"DAI".to_vec()),
+                vec![(18, 102), (24, 103), (30, 104)]
+            );
+        });
+    }
+
+    #[test]
+    fn price_sources_should_be_managed_by_governance() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(PriceOracleModule::fetched_sources().len(), FETCHED_CRYPTOS.len());
+            assert_eq!(
+                PriceOracleModule::price_source(b"DAI".to_vec()),
+                (b"coincap".to_vec(), b"https://api.coincap.io/v2/assets/dai".to_vec())
+            );
+
+            let url = b"https://api.coincap.io/v2/assets/wrapped-bitcoin".to_vec();
+            assert_noop!(
+                PriceOracleModule::add_price_source(Origin::NONE, b"WBTC".to_vec(), b"coincap".to_vec(), url.clone()),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                PriceOracleModule::add_price_source(Origin::ROOT, b"WBTC".to_vec(), b"unknown".to_vec(), url.clone()),
+                DispatchError::Other("Unknown remote source")
+            );
+            assert_ok!(PriceOracleModule::add_price_source(
+                Origin::ROOT,
+                b"WBTC".to_vec(),
+                b"coincap".to_vec(),
+                url.clone()
+            ));
+            let sources = PriceOracleModule::fetched_sources();
+            assert_eq!(sources.len(), FETCHED_CRYPTOS.len() + 1);
+            assert!(sources.contains(&(b"WBTC".to_vec(), b"coincap".to_vec(), url)));
+
+            assert_ok!(PriceOracleModule::remove_price_source(Origin::ROOT, b"WBTC".to_vec()));
+            assert_noop!(
+                PriceOracleModule::remove_price_source(Origin::ROOT, b"WBTC".to_vec()),
+                DispatchError::Other("Unknown price source")
+            );
+            assert_eq!(PriceOracleModule::fetched_sources().len(), FETCHED_CRYPTOS.len());
+        });
+    }
+
+    #[test]
+    fn twap_should_weight_prices_by_duration() {
+        new_test_ext().execute_with(|| {
+            let crypto_info = (b"DAI".to_vec(), b"coincap".to_vec(), b"url".to_vec());
+            for (now, price) in [(1_000, 100), (1_010, 200), (1_040, 400)].iter() {
+                timestamp::Module::<Test>::set_timestamp(*now);
+                assert_ok!(PriceOracleModule::record_price_unsigned(
+                    Origin::NONE,
+                    1,
+                    crypto_info.clone(),
+                    *price
+                ));
+            }
+            timestamp::Module::<Test>::set_timestamp(1_050);
+
+            // (100 * 10 + 200 * 30 + 400 * 10) / 50, the plain mean is 233
+            assert_eq!(PriceOracleModule::twap(b"DAI".to_vec(), 50), Some(220));
+            assert_eq!(PriceOracleModule::average_price(&[100, 200, 400]), Ok(233));
+            // 200 holds for the first 10 of the last 20
+            assert_eq!(PriceOracleModule::twap(b"DAI".to_vec(), 20), Some(300));
+            assert_eq!(PriceOracleModule::twap(b"DAI".to_vec(), 5), Some(400));
+            assert_eq!(PriceOracleModule::twap(b"USDT".to_vec(), 50), None);
+        });
+    }
+
+    #[test]
+    fn new_token_should_warm_up_before_use() {
+        new_test_ext().execute_with(|| {
+            let crypto_info = (b"BTC".to_vec(), b"coincap".to_vec(), b"url".to_vec());
+            assert_ok!(PriceOracleModule::add_tracked_token(
+                Origin::ROOT,
+                crypto_info.0.clone(),
+                crypto_info.1.clone(),
+                crypto_info.2.clone()
+            ));
+            assert!(PriceOracleModule::is_warming_up(b"BTC"));
+
+            for price in [100, 101].iter() {
+                assert_ok!(PriceOracleModule::record_price_unsigned(
+                    Origin::NONE,
+                    1,
+                    crypto_info.clone(),
+                    *price
+                ));
+            }
+            assert!(PriceOracleModule::is_warming_up(b"BTC"));
+
+            assert_ok!(PriceOracleModule::record_price_unsigned(Origin::NONE, 1, crypto_info.clone(), 102));
+            assert!(!PriceOracleModule::is_warming_up(b"BTC"));
+
+            // tracked again from scratch
+            assert_ok!(PriceOracleModule::remove_tracked_token(Origin::ROOT, b"BTC".to_vec()));
+            assert!(PriceOracleModule::is_warming_up(b"BTC"));
+        });
+    }
 }
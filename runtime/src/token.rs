@@ -8,7 +8,7 @@ use frame_support::{
     weights::SimpleDispatchInfo, StorageMap,
 };
 use num_traits::ops::checked::{CheckedAdd, CheckedSub};
-use sp_runtime::traits::{StaticLookup, Zero};
+use sp_runtime::traits::{Saturating, StaticLookup, Zero};
 use sp_std::prelude::Vec;
 use system::{self, ensure_signed};
 
@@ -20,7 +20,7 @@ decl_event!(
         AccountId = <T as system::Trait>::AccountId,
         Balance = <T as balances::Trait>::Balance,
     {
-        Transfer(AccountId, AccountId, Balance),
+        Transferred(TokenId, AccountId, AccountId, Balance),
         Approval(AccountId, AccountId, Balance),
         Mint(AccountId, Balance),
         Burn(AccountId, Balance),
@@ -94,8 +94,9 @@ decl_module! {
         //     ensure_signed(origin)?;
         //     Self::check_token_exist(&token)
         // }
+        // moves unlocked balance of the token to another account
         #[weight = SimpleDispatchInfo::FixedNormal(10_000)]
-        fn transfer(origin,
+        pub fn transfer(origin,
             to: <T::Lookup as StaticLookup>::Source,
             token_id: TokenId,
             #[compact] amount: T::Balance
@@ -152,8 +153,7 @@ impl<T: Trait> Module<T> {
             "Cannot burn more than total supply"
         );
 
-        let free_balance = <Balance<T>>::get((token_id, from.clone()))
-            - <Locked<T>>::get((token_id, from.clone()));
+        let free_balance = Self::free_balance(token_id, &from);
         ensure!(
             free_balance > T::Balance::zero(),
             "Cannot burn with zero balance"
@@ -197,23 +197,49 @@ impl<T: Trait> Module<T> {
     ) -> Result<()> {
         let from_balance = <Balance<T>>::get((token_id, from.clone()));
         ensure!(from_balance >= amount, "User does not have enough tokens");
-        let free_balance = <Balance<T>>::get((token_id, from.clone()))
-            - <Locked<T>>::get((token_id, from.clone()));
+        let free_balance = Self::free_balance(token_id, &from);
         ensure!(free_balance >= amount, "Not enough because of locked funds");
 
-        <Balance<T>>::insert((token_id, from.clone()), from_balance - amount);
-        <Balance<T>>::mutate((token_id, to.clone()), |balance| *balance += amount);
+        if from != to {
+            let next_from_balance = from_balance
+                .checked_sub(&amount)
+                .ok_or("Underflow subtracting from balance")?;
+            let next_to_balance = <Balance<T>>::get((token_id, to.clone()))
+                .checked_add(&amount)
+                .ok_or("Overflow adding to balance")?;
+            <Balance<T>>::insert((token_id, from.clone()), next_from_balance);
+            <Balance<T>>::insert((token_id, to.clone()), next_to_balance);
+        }
 
-        Self::deposit_event(RawEvent::Transfer(from, to, amount));
+        Self::deposit_event(RawEvent::Transferred(token_id, from, to, amount));
 
         Ok(())
     }
+    /// balance of the account not locked for a pending withdraw
+    pub fn free_balance(token_id: TokenId, account: &T::AccountId) -> T::Balance {
+        let key = (token_id, account.clone());
+        <Balance<T>>::get(&key).saturating_sub(<Locked<T>>::get(&key))
+    }
+
+    /// (total, locked, free) balance of the account
+    pub fn account_data(token_id: TokenId, account: &T::AccountId) -> (T::Balance, T::Balance, T::Balance) {
+        let key = (token_id, account.clone());
+        (
+            <Balance<T>>::get(&key),
+            <Locked<T>>::get(&key),
+            Self::free_balance(token_id, account),
+        )
+    }
+
     pub fn lock(token_id: TokenId, account: T::AccountId, amount: T::Balance) -> Result<()> {
         //TODO: substract this amount from the main balance?
         //              Balance: 1000, Locked: 0
         // lock(400) => Balance: 1000, Locked: 400 or
         // lock(400) => Balance: 600, Locked: 400
-        <Locked<T>>::insert((token_id, account.clone()), amount);
+        let locked = <Locked<T>>::get((token_id, account.clone()))
+            .checked_add(&amount)
+            .ok_or("Overflow while locking")?;
+        <Locked<T>>::insert((token_id, account), locked);
 
         Ok(())
     }
@@ -225,7 +251,7 @@ impl<T: Trait> Module<T> {
         let balance = <Locked<T>>::get((token_id, account.clone()));
         let new_balance = balance
             .checked_sub(&amount)
-            .expect("Underflow while unlocking. Check if user has enough locked funds.");
+            .ok_or("Underflow while unlocking. Check if user has enough locked funds.")?;
         let zero = T::Balance::zero();
 
         match new_balance {
@@ -460,6 +486,40 @@ mod tests {
         })
     }
 
+    #[test]
+    fn token_lock_should_add_up() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(TokenModule::_mint(TOKEN_ID, USER2, 1000));
+
+            assert_ok!(TokenModule::lock(TOKEN_ID, USER2, 400));
+            assert_ok!(TokenModule::lock(TOKEN_ID, USER2, 100));
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 500);
+            assert_noop!(
+                TokenModule::unlock(TOKEN_ID, &USER2, 600),
+                "Underflow while unlocking. Check if user has enough locked funds."
+            );
+            assert_ok!(TokenModule::unlock(TOKEN_ID, &USER2, 500));
+            assert_eq!(TokenModule::locked((TOKEN_ID, USER2)), 0);
+        })
+    }
+
+    #[test]
+    fn free_balance_should_exclude_locked_funds() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(TokenModule::_mint(TOKEN_ID, USER2, 1000));
+            assert_eq!(TokenModule::free_balance(TOKEN_ID, &USER2), 1000);
+
+            assert_ok!(TokenModule::lock(TOKEN_ID, USER2, 400));
+            assert_eq!(TokenModule::free_balance(TOKEN_ID, &USER2), 600);
+            assert_eq!(TokenModule::account_data(TOKEN_ID, &USER2), (1000, 400, 600));
+
+            assert_ok!(TokenModule::unlock(TOKEN_ID, &USER2, 400));
+            assert_eq!(TokenModule::free_balance(TOKEN_ID, &USER2), 1000);
+            assert_eq!(TokenModule::account_data(TOKEN_ID, &USER2), (1000, 0, 1000));
+            assert_eq!(TokenModule::account_data(TOKEN_ID, &USER1), (0, 0, 0));
+        })
+    }
+
     #[test]
     fn token_transfer_not_enough() {
         ExtBuilder::default().build().execute_with(|| {
@@ -482,6 +542,30 @@ mod tests {
         })
     }
     #[test]
+    fn token_transfer_should_keep_locked_funds() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(TokenModule::_mint(TOKEN_ID, USER2, 1000));
+            assert_ok!(TokenModule::lock(TOKEN_ID, USER2, 400));
+
+            assert_noop!(
+                TokenModule::transfer(Origin::signed(USER2), USER1, TOKEN_ID, 700),
+                "Not enough because of locked funds"
+            );
+            assert_ok!(TokenModule::transfer(
+                Origin::signed(USER2),
+                USER1,
+                TOKEN_ID,
+                600
+            ));
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER2)), 400);
+            assert_eq!(TokenModule::balance_of((TOKEN_ID, USER1)), 600);
+            assert_noop!(
+                TokenModule::transfer(Origin::signed(USER2), USER1, TOKEN_ID, 1),
+                "Not enough because of locked funds"
+            );
+        })
+    }
+    #[test]
     fn token_transfer_burn_works() {
         ExtBuilder::default().build().execute_with(|| {
             assert_ok!(TokenModule::_mint(TOKEN_ID, USER2, 1000));
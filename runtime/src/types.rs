@@ -10,6 +10,8 @@ pub type Count = u64;
 pub type DaoId = u64;
 pub type MemberId = u64;
 pub type ProposalId = u64;
+// ethereum chain id of a bridge message
+pub type ChainId = u32;
 pub type VotesCount = MemberId;
 pub type Days = u32;
 pub type Rate = u32;
@@ -67,6 +69,33 @@ pub enum Action<AccountId, Balance, Timeout> {
     ChangeMaximumNumberOfMembers(DaoId, MemberId),
 }
 
+//oracle
+#[derive(Encode, Decode, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum OracleMode {
+    Live,
+    Fixed,
+}
+
+impl Default for OracleMode {
+    fn default() -> Self {
+        OracleMode::Live
+    }
+}
+
+#[derive(Encode, Decode, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum AggregationMethod {
+    Mean,
+    Median,
+}
+
+impl Default for AggregationMethod {
+    fn default() -> Self {
+        AggregationMethod::Mean
+    }
+}
+
 //token factory
 pub type TokenId = u32;
 
@@ -79,7 +108,7 @@ pub struct Token {
 }
 
 //bridge
-#[derive(Encode, Decode, Clone, PartialEq)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(Debug))]
 pub struct Limits<Balance> {
     pub max_tx_value: Balance,
@@ -100,8 +129,105 @@ pub struct BridgeTransfer<Hash> {
     pub kind: Kind,
 }
 
+// page of bridge state for off-chain reconciliation,
+// `next` is the open proposal id to continue from
+#[derive(Encode, Decode, Clone)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct BridgeSnapshot<Hash, Balance> {
+    pub transfers: Vec<(BridgeTransfer<Hash>, Status)>,
+    pub pending_burn: Balance,
+    pub pending_mint: Balance,
+    pub limits: Limits<Balance>,
+    pub next: Option<ProposalId>,
+}
+
+// bridge config in effect at some block
 #[derive(Encode, Decode, Clone, PartialEq)]
 #[cfg_attr(feature = "std", derive(Debug))]
+pub struct BridgeConfigSnapshot<Balance> {
+    pub limits: Limits<Balance>,
+    pub validators_count: u32,
+    pub quorum: u64,
+}
+
+// message behind a proposal id, resolved by its kind
+#[derive(Encode, Decode, Clone)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum ProposalMessage<AccountId, Hash, Balance> {
+    Transfer(TransferMessage<AccountId, Hash, Balance>),
+    Limits(LimitMessage<Hash, Balance>),
+    Validator(ValidatorMessage<AccountId, Hash>),
+    Bridge(BridgeMessage<AccountId, Hash>),
+}
+
+impl<A, H, B> ProposalMessage<A, H, B> {
+    pub fn kind(&self) -> Kind {
+        match self {
+            ProposalMessage::Transfer(_) => Kind::Transfer,
+            ProposalMessage::Limits(_) => Kind::Limits,
+            ProposalMessage::Validator(_) => Kind::Validator,
+            ProposalMessage::Bridge(_) => Kind::Bridge,
+        }
+    }
+}
+
+// (genesis, current) pairs of the bridge config values that changed since genesis
+#[derive(Encode, Decode, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct ConfigDiff<AccountId, Balance> {
+    pub limits: Option<(Limits<Balance>, Limits<Balance>)>,
+    pub validators: Option<(Vec<AccountId>, Vec<AccountId>)>,
+    pub quorum: Option<(u64, u64)>,
+}
+
+impl<A, B> Default for ConfigDiff<A, B> {
+    fn default() -> Self {
+        ConfigDiff {
+            limits: None,
+            validators: None,
+            quorum: None,
+        }
+    }
+}
+
+// per-block rollup of bridge activity
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct BridgeDigest {
+    pub transfers_opened: u32,
+    pub approvals: u32,
+    pub confirmations: u32,
+    pub cancellations: u32,
+}
+
+// soft rejections of withdraws counted for monitoring
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum RejectionReason {
+    DailyLimit,
+    PendingCapacity,
+}
+
+// status a withdraw would end in, or the check declining it
+#[derive(Encode, Decode, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum SimulatedOutcome {
+    Accepted,
+    BridgeNotOperational,
+    ValidatorsNotReady,
+    TokenTransfersDisabled,
+    SystemAccount,
+    InvalidDestination,
+    ComplianceRejected,
+    BelowMinimum,
+    AboveMaximum,
+    PendingLimitReached,
+    DailyLimitReached,
+    ContractDestination,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Deserialize, Serialize, Debug))]
 pub enum Status {
     Revoked,
     Pending,
@@ -114,6 +240,10 @@ pub enum Status {
     Approved,
     Canceled,
     Confirmed,
+    CancelValidatorChange,
+    UpdateTokenAddress,
+    UpdateQuorum,
+    WithdrawLimitProposal,
 }
 
 #[derive(Encode, Decode, Clone, PartialEq)]
@@ -129,6 +259,7 @@ pub enum Kind {
 #[cfg_attr(feature = "std", derive(Debug))]
 pub struct TransferMessage<AccountId, Hash, Balance> {
     pub message_id: Hash,
+    pub chain_id: ChainId,
     pub token: TokenId,
     pub eth_address: H160,
     pub substrate_address: AccountId,
@@ -142,6 +273,8 @@ pub struct TransferMessage<AccountId, Hash, Balance> {
 pub struct LimitMessage<Hash, Balance> {
     pub id: Hash,
     pub limits: Limits<Balance>,
+    // None changes the limits shared by tokens without their own
+    pub token: Option<TokenId>,
     pub status: Status,
 }
 
@@ -173,6 +306,7 @@ where
     fn default() -> Self {
         TransferMessage {
             message_id: H::default(),
+            chain_id: ChainId::default(),
             token: TokenId::default(),
             eth_address: H160::default(),
             substrate_address: A::default(),
@@ -192,6 +326,7 @@ where
         LimitMessage {
             id: H::default(),
             limits: Limits::default(),
+            token: None,
             status: Status::UpdateLimits,
         }
     }
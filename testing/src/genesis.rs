@@ -130,5 +130,6 @@ pub fn config_endowed(
 			decimals: 18,
 			symbol: Vec::from("TOKEN"),
 		}] }),
+        price_oracle: Some(Default::default()),
     }
 }